@@ -0,0 +1,176 @@
+use std::collections::{HashMap, VecDeque};
+
+/// An occurrence of `patterns[pattern]` at the byte range `start..end`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Finds every occurrence of a set of patterns, overlapping ones included,
+/// in a single pass over the haystack.
+///
+/// Patterns are matched byte by byte, so any UTF-8 vocabulary works and
+/// match positions always fall on character boundaries.
+pub struct AhoCorasick {
+    transitions: Vec<HashMap<u8, usize>>,
+    /// The state of the longest proper suffix that is also in the trie.
+    fail: Vec<usize>,
+    /// The patterns ending in every state, including through its fail links.
+    outputs: Vec<Vec<usize>>,
+    pattern_lens: Vec<usize>,
+}
+
+impl AhoCorasick {
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        let mut matcher = Self {
+            transitions: vec![HashMap::new()],
+            fail: vec![0],
+            outputs: vec![vec![]],
+            pattern_lens: vec![],
+        };
+
+        patterns
+            .into_iter()
+            .enumerate()
+            .for_each(|(index, pattern)| {
+                let pattern = pattern.as_ref().as_bytes();
+                assert!(!pattern.is_empty(), "Patterns must not be empty.");
+                let mut state = 0;
+                for &byte in pattern {
+                    state = match matcher.transitions[state].get(&byte) {
+                        Some(&next) => next,
+                        None => {
+                            let next = matcher.transitions.len();
+                            matcher.transitions.push(HashMap::new());
+                            matcher.fail.push(0);
+                            matcher.outputs.push(vec![]);
+                            matcher.transitions[state].insert(byte, next);
+                            next
+                        }
+                    };
+                }
+                matcher.outputs[state].push(index);
+                matcher.pattern_lens.push(pattern.len());
+            });
+
+        // breadth first, so the fail state of every state is already complete
+        let mut queue: VecDeque<usize> = matcher.transitions[0].values().copied().collect();
+        while let Some(state) = queue.pop_front() {
+            let children: Vec<(u8, usize)> = matcher.transitions[state]
+                .iter()
+                .map(|(&byte, &child)| (byte, child))
+                .collect();
+            for (byte, child) in children {
+                let fail = match state {
+                    0 => 0,
+                    _ => matcher.step(matcher.fail[state], byte),
+                };
+                matcher.fail[child] = fail;
+                let inherited = matcher.outputs[fail].clone();
+                matcher.outputs[child].extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        matcher
+    }
+
+    fn step(&self, mut state: usize, byte: u8) -> usize {
+        loop {
+            if let Some(&next) = self.transitions[state].get(&byte) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.fail[state];
+        }
+    }
+
+    /// Every match in order of where it ends, overlapping ones included.
+    pub fn find_overlapping_iter<'a>(
+        &'a self,
+        haystack: &'a str,
+    ) -> impl Iterator<Item = Match> + 'a {
+        haystack
+            .bytes()
+            .enumerate()
+            .scan(0, move |state, (index, byte)| {
+                *state = self.step(*state, byte);
+                Some((index + 1, *state))
+            })
+            .flat_map(move |(end, state)| {
+                self.outputs[state].iter().map(move |&pattern| Match {
+                    pattern,
+                    start: end - self.pattern_lens[pattern],
+                    end,
+                })
+            })
+    }
+
+    /// The matches starting first and last, found in a single pass.
+    pub fn first_and_last(&self, haystack: &str) -> Option<(Match, Match)> {
+        self.find_overlapping_iter(haystack)
+            .fold(None, |bounds, found| match bounds {
+                None => Some((found, found)),
+                Some((first, last)) => Some((
+                    if found.start < first.start {
+                        found
+                    } else {
+                        first
+                    },
+                    if found.start > last.start {
+                        found
+                    } else {
+                        last
+                    },
+                )),
+            })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AhoCorasick, Match};
+
+    #[test]
+    fn overlapping_matches() {
+        let matcher = AhoCorasick::new(["he", "she", "his", "hers"]);
+        let matches: Vec<(usize, usize)> = matcher
+            .find_overlapping_iter("ushers")
+            .map(|found| (found.pattern, found.start))
+            .collect();
+        assert_eq!(matches, vec![(1, 1), (0, 2), (3, 2)]);
+    }
+
+    #[test]
+    fn first_and_last() {
+        let matcher = AhoCorasick::new(["one", "eight", "two"]);
+        assert_eq!(
+            matcher.first_and_last("xoneightwo"),
+            Some((
+                Match {
+                    pattern: 0,
+                    start: 1,
+                    end: 4
+                },
+                Match {
+                    pattern: 2,
+                    start: 7,
+                    end: 10
+                },
+            ))
+        );
+        assert_eq!(matcher.first_and_last("nothing"), None);
+
+        let matcher = AhoCorasick::new(["fünf", "ünf"]);
+        let (first, last) = matcher.first_and_last("xfünf").unwrap();
+        assert_eq!((first.start, last.start), (1, 2));
+    }
+}
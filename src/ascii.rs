@@ -0,0 +1,32 @@
+/// The ASCII code of every character of `value`, a stable stand-in for
+/// `char::as_ascii`.
+///
+/// Panics on characters outside of ASCII.
+pub fn ascii_codes(value: &str) -> impl Iterator<Item = u8> + '_ {
+    value.chars().map(|ch| {
+        u8::try_from(ch)
+            .ok()
+            .filter(u8::is_ascii)
+            .expect("Expected an ASCII string.")
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ascii_codes;
+
+    #[test]
+    fn codes() {
+        assert_eq!(
+            ascii_codes("rn=1").collect::<Vec<_>>(),
+            vec![114, 110, 61, 49]
+        );
+        assert_eq!(ascii_codes("").count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_ascii() {
+        ascii_codes("é").for_each(drop);
+    }
+}
@@ -1,74 +1,82 @@
-use regex::Regex;
+use advent_of_code::{AhoCorasick, Puzzle};
 
 advent_of_code::solution!(1);
 
-fn find_first_digit(haystack: &str, re: &Regex) -> Option<u32> {
-    let digit_or_name = re.captures(haystack)?.get(0)?.as_str();
-    let digit = match digit_or_name {
-        "one" => 1,
-        "two" => 2,
-        "three" => 3,
-        "four" => 4,
-        "five" => 5,
-        "six" => 6,
-        "seven" => 7,
-        "eight" => 8,
-        "nine" => 9,
-        _ => digit_or_name.parse().unwrap(),
-    };
-    Some(digit)
-}
+const DIGITS: [(&str, u32); 9] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+const SPELLED_DIGITS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
 
-fn find_last_digit(haystack: &str, re: &Regex) -> Option<u32> {
-    let reversed_haystack = haystack.chars().rev().collect::<String>();
-    let digit_or_name = re.captures(&reversed_haystack)?.get(0)?.as_str();
-    let digit = match digit_or_name {
-        "eno" => 1,
-        "owt" => 2,
-        "eerht" => 3,
-        "ruof" => 4,
-        "evif" => 5,
-        "xis" => 6,
-        "neves" => 7,
-        "thgie" => 8,
-        "enin" => 9,
-        _ => digit_or_name.parse().unwrap(),
-    };
-    Some(digit)
+/// The tokens that count as digits and the value of each.
+struct Vocabulary {
+    matcher: AhoCorasick,
+    values: Vec<u32>,
 }
 
-fn get_calibration_value(first_digit: Option<u32>, last_digit: Option<u32>) -> Option<u32> {
-    match (first_digit, last_digit) {
-        (Some(first_digit), Some(last_digit)) => {
-            let value = first_digit * 10 + last_digit;
-            Some(value)
+impl Vocabulary {
+    fn new<'a>(tokens: impl IntoIterator<Item = &'a (&'a str, u32)>) -> Self {
+        let (tokens, values): (Vec<&str>, Vec<u32>) = tokens.into_iter().copied().unzip();
+        Self {
+            matcher: AhoCorasick::new(tokens),
+            values,
         }
-        _ => None,
+    }
+
+    fn calibration_value(&self, line: &str) -> Option<u32> {
+        let (first, last) = self.matcher.first_and_last(line)?;
+        Some(self.values[first.pattern] * 10 + self.values[last.pattern])
     }
 }
 
-fn solve(input: &str, re: &Regex, reversed_re: &Regex) -> Option<u32> {
+fn solve(lines: &[&str], vocabulary: &Vocabulary) -> Option<u32> {
     Some(
-        input
-            .lines()
-            .map(|line| {
-                let first_digit = find_first_digit(line, re);
-                let last_digit = find_last_digit(line, reversed_re);
-                get_calibration_value(first_digit, last_digit).unwrap_or_default()
-            })
+        lines
+            .iter()
+            .map(|line| vocabulary.calibration_value(line).unwrap_or_default())
             .sum(),
     )
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let re = Regex::new(r"(\d)").unwrap();
-    solve(input, &re, &re)
-}
+struct Solution;
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let re = Regex::new(r"(\d|one|two|three|four|five|six|seven|eight|nine)").unwrap();
-    let reversed_re = Regex::new(r"(\d|eno|owt|eerht|ruof|evif|xis|neves|thgie|enin)").unwrap();
-    solve(input, &re, &reversed_re)
+impl Puzzle for Solution {
+    type Parsed<'a> = Vec<&'a str>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        input.lines().collect()
+    }
+
+    fn part_one(lines: &Self::Parsed<'_>) -> Option<u32> {
+        solve(lines, &Vocabulary::new(&DIGITS))
+    }
+
+    fn part_two(lines: &Self::Parsed<'_>) -> Option<u32> {
+        solve(
+            lines,
+            &Vocabulary::new(DIGITS.iter().chain(&SPELLED_DIGITS)),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -90,4 +98,20 @@ mod tests {
         ));
         assert_eq!(result, Some(281));
     }
+
+    #[test]
+    fn test_overlapping_words() {
+        let vocabulary = Vocabulary::new(DIGITS.iter().chain(&SPELLED_DIGITS));
+        assert_eq!(vocabulary.calibration_value("eightwo"), Some(82));
+        assert_eq!(vocabulary.calibration_value("xtwone3four"), Some(24));
+        assert_eq!(vocabulary.calibration_value("abc"), None);
+    }
+
+    #[test]
+    fn test_other_language() {
+        let german = [("eins", 1), ("zwei", 2), ("drei", 3), ("fünf", 5)];
+        let vocabulary = Vocabulary::new(DIGITS.iter().chain(&german));
+        assert_eq!(vocabulary.calibration_value("zwei1drei"), Some(23));
+        assert_eq!(vocabulary.calibration_value("fünfzweins"), Some(51));
+    }
 }
@@ -1,108 +1,121 @@
+use advent_of_code::Puzzle;
+use std::collections::HashMap;
+
 advent_of_code::solution!(2);
 
-struct Set {
-    red: u32,
-    green: u32,
-    blue: u32,
-}
+/// The bag the Elf asks about in part one.
+const ELF_BAG: &str = "12 red, 13 green, 14 blue";
+
+/// A number of cubes per color, such as a handful revealed or a bag.
+/// Colors missing from the map count as zero cubes.
+#[derive(Clone, Default, PartialEq, Debug)]
+struct Set(HashMap<String, u32>);
 
 struct Game {
     id: u32,
     sets: Vec<Set>,
 }
 
-fn parse_set(set: &str) -> Set {
-    let (mut red, mut green, mut blue) = (0, 0, 0);
-    let cubes = set.split(',');
-
-    cubes.for_each(|cube| {
-        let mut info = cube.split_ascii_whitespace();
-        let count = info.next().unwrap().parse().unwrap();
-        let color = info.next().unwrap();
-        match color {
-            "red" => red = count,
-            "green" => green = count,
-            "blue" => blue = count,
-            _ => panic!("other color"),
-        }
-    });
-
-    Set { red, green, blue }
+impl From<&str> for Set {
+    fn from(value: &str) -> Self {
+        Self(
+            value
+                .split(',')
+                .map(|cube| {
+                    let (count, color) = cube.trim().split_once(' ').unwrap();
+                    (color.to_string(), count.parse().unwrap())
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Set {
+    fn count(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    fn colors(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    fn fits_in(&self, bag: &Set) -> bool {
+        self.0
+            .iter()
+            .all(|(color, &count)| count <= bag.count(color))
+    }
+
+    /// The product of the counts of `colors`.
+    fn power<'a>(&self, colors: impl IntoIterator<Item = &'a str>) -> u32 {
+        colors.into_iter().map(|color| self.count(color)).product()
+    }
 }
 
 fn parse_line(line: &str) -> Game {
-    let mut id_and_sets = line.split(": ");
-
-    let id = id_and_sets
-        .next()
-        .unwrap()
-        .split_ascii_whitespace()
-        .last()
-        .unwrap()
-        .parse()
-        .unwrap();
-
-    let sets = id_and_sets
-        .next()
-        .unwrap()
-        .split(';')
-        .map(parse_set)
-        .collect();
+    let (game, sets) = line.split_once(": ").unwrap();
+    let id = game.strip_prefix("Game ").unwrap().parse().unwrap();
+    let sets = sets.split(';').map(Set::from).collect();
 
     Game { id, sets }
 }
 
-fn is_possible(game: &Game) -> bool {
-    for set in game.sets.iter() {
-        let &Set { red, green, blue } = set;
-        if red > 12 || green > 13 || blue > 14 {
-            return false;
-        }
+impl Game {
+    fn is_possible(&self, bag: &Set) -> bool {
+        self.sets.iter().all(|set| set.fits_in(bag))
     }
-    true
-}
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(parse_line)
-            .filter(is_possible)
-            .map(|game| game.id)
-            .sum(),
-    )
+    /// The fewest cubes of every color shown that make the game possible.
+    fn minimum_bag(&self) -> Set {
+        let mut bag = Set::default();
+        self.sets
+            .iter()
+            .flat_map(|set| &set.0)
+            .for_each(|(color, &count)| {
+                let fewest = bag.0.entry(color.clone()).or_default();
+                *fewest = (*fewest).max(count);
+            });
+        bag
+    }
 }
 
-fn fewest_set(game: &Game) -> Set {
-    let (mut red, mut green, mut blue) = (0, 0, 0);
-    game.sets.iter().for_each(|set| {
-        if red < set.red {
-            red = set.red;
-        }
-        if blue < set.blue {
-            blue = set.blue;
-        }
-        if green < set.green {
-            green = set.green;
-        }
-    });
-
-    Set { red, green, blue }
+/// The sum of the ids of the games possible with `bag`.
+fn possible_games(games: &[Game], bag: &Set) -> u32 {
+    games
+        .iter()
+        .filter(|game| game.is_possible(bag))
+        .map(|game| game.id)
+        .sum()
 }
 
-fn multiply(set: &Set) -> u32 {
-    set.red * set.green * set.blue
+/// The sum of the powers of the minimum bags over `colors`, so a game that
+/// never shows a color has a power of zero.
+fn minimum_powers(games: &[Game], colors: &[&str]) -> u32 {
+    games
+        .iter()
+        .map(|game| game.minimum_bag().power(colors.iter().copied()))
+        .sum()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(parse_line)
-            .map(|game| fewest_set(&game))
-            .map(|set| multiply(&set))
-            .sum(),
-    )
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Vec<Game>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Vec<Game> {
+        input.lines().map(parse_line).collect()
+    }
+
+    fn part_one(games: &Vec<Game>) -> Option<u32> {
+        Some(possible_games(games, &Set::from(ELF_BAG)))
+    }
+
+    fn part_two(games: &Vec<Game>) -> Option<u32> {
+        let bag = Set::from(ELF_BAG);
+        let colors: Vec<&str> = bag.colors().collect();
+        Some(minimum_powers(games, &colors))
+    }
 }
 
 #[cfg(test)]
@@ -120,4 +133,17 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2286));
     }
+
+    #[test]
+    fn test_other_colors() {
+        let games =
+            Solution::parse("Game 1: 3 purple, 2 red; 5 purple\nGame 2: 1 teal; 2 red, 1 teal");
+        assert_eq!(possible_games(&games, &Set::from("5 purple, 2 red")), 1);
+        assert_eq!(possible_games(&games, &Set::from("1 teal, 2 red")), 2);
+        assert_eq!(minimum_powers(&games, &["purple", "red"]), 10);
+        assert_eq!(minimum_powers(&games, &["red", "teal"]), 2);
+
+        let game = parse_line("Game 7: 1 teal; 2 red, 1 teal");
+        assert_eq!(game.minimum_bag(), Set::from("2 red, 1 teal"));
+    }
 }
@@ -1,12 +1,12 @@
-use advent_of_code::{Matrix, Position as P};
-use std::collections::{HashMap, HashSet};
+use advent_of_code::{Matrix, Orientation, Position as P, Puzzle, Span};
+use std::collections::HashMap;
 type Position = P<usize>;
 
 advent_of_code::solution!(3);
 
 struct Number {
     value: u32,
-    positions: Vec<Position>,
+    span: Span,
 }
 
 struct Engine {
@@ -16,43 +16,21 @@ struct Engine {
 
 impl Engine {
     fn new(schematic_text: &str) -> Self {
-        let (mut schematic, mut numbers) = (vec![], vec![]);
-        let (mut row, mut col) = (0, 0);
-        schematic_text.lines().for_each(|line| {
-            row += 1;
-            col = 0;
-            let mut chars = vec![];
-            let mut number = String::new();
-            let mut positions = vec![];
-            line.chars().for_each(|c| {
-                col += 1;
-                chars.push(c);
-
-                if c.is_ascii_digit() {
-                    number.push(c);
-                    positions.push((row, col).into())
-                } else if !number.is_empty() {
-                    numbers.push(Self::make_number_and_clear(&mut number, &mut positions));
-                }
-            });
-            if !number.is_empty() {
-                numbers.push(Self::make_number_and_clear(&mut number, &mut positions));
-            }
-            schematic.push(chars);
-        });
-        Self {
-            schematic: schematic.into(),
-            numbers,
-        }
-    }
-
-    fn make_number_and_clear(number: &mut String, positions: &mut Vec<Position>) -> Number {
-        let num = Number {
-            value: number.parse().unwrap(),
-            positions: std::mem::take(positions),
-        };
-        number.clear();
-        num
+        let schematic: Matrix<char> = Matrix::from(schematic_text);
+        let numbers = schematic
+            .runs(Orientation::Horizontal, char::is_ascii_digit)
+            .into_iter()
+            .map(|span| Number {
+                value: schematic
+                    .span_values(&span)
+                    .into_iter()
+                    .collect::<String>()
+                    .parse()
+                    .unwrap(),
+                span,
+            })
+            .collect();
+        Self { schematic, numbers }
     }
 
     fn get_char(&self, pos: &Position) -> char {
@@ -79,37 +57,8 @@ impl Engine {
         false
     }
 
-    fn get_adjacent_positions(&self, pos: &Position) -> Vec<Position> {
-        let Position { row, col } = *pos;
-        [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ]
-        .map(|(diff_row, diff_col)| (row as i32 + diff_row, col as i32 + diff_col))
-        .map(|(row, col)| (row as usize, col as usize).into())
-        .into_iter()
-        .filter(|pos| self.schematic.is_valid_position(pos))
-        .collect()
-    }
-
     fn get_number_adjacent_position(&self, number: &Number) -> Vec<Position> {
-        let mut adjacent_positions = HashSet::new();
-
-        number
-            .positions
-            .iter()
-            .flat_map(|pos| self.get_adjacent_positions(pos))
-            .for_each(|adjacent_pos| {
-                adjacent_positions.insert(adjacent_pos);
-            });
-
-        Vec::from_iter(adjacent_positions)
+        self.schematic.span_border(&number.span)
     }
 
     fn get_part_numbers(&self) -> Vec<u32> {
@@ -148,12 +97,24 @@ impl Engine {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(Engine::new(input).get_part_numbers().iter().sum())
-}
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Engine;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Engine {
+        Engine::new(input)
+    }
+
+    fn part_one(engine: &Engine) -> Option<u32> {
+        Some(engine.get_part_numbers().iter().sum())
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    Some(Engine::new(input).get_gear_ratios().iter().sum())
+    fn part_two(engine: &Engine) -> Option<u32> {
+        Some(engine.get_gear_ratios().iter().sum())
+    }
 }
 
 #[cfg(test)]
@@ -1,87 +1,90 @@
-use std::collections::{HashMap, HashSet};
+use advent_of_code::Puzzle;
 
 advent_of_code::solution!(4);
 
+/// The numbers on a card, one bit each.
+type Numbers = u128;
+
 struct Card {
-    id: u32,
-    win_set: HashSet<u32>,
-    numbers: Vec<u32>,
+    winning: Numbers,
+    numbers: Numbers,
+}
+
+fn parse_numbers(value: &str) -> Numbers {
+    value
+        .split_ascii_whitespace()
+        .map(|number| number.parse::<u32>().unwrap())
+        .fold(0, |bits, number| {
+            assert!(number < Numbers::BITS, "Card numbers must be below 128.");
+            bits | 1 << number
+        })
 }
 
 impl Card {
     fn new(card_line: &str) -> Self {
-        let mut iter = card_line.split(&[':', '|'][..]).map(|x| x.trim());
-
-        let id = iter
-            .next()
-            .unwrap()
-            .split_ascii_whitespace()
-            .last()
-            .unwrap()
-            .parse()
-            .unwrap();
-
-        let mut next_numbers = || {
-            iter.next()
-                .unwrap()
-                .split_ascii_whitespace()
-                .map(|num_str| num_str.parse::<u32>().unwrap())
-                .collect::<Vec<u32>>()
-        };
-
-        let win_numbers = next_numbers();
-        let numbers = next_numbers();
-
-        let win_set = HashSet::from_iter(win_numbers);
+        let (_, numbers) = card_line.split_once(':').unwrap();
+        let (winning, numbers) = numbers.split_once('|').unwrap();
 
         Self {
-            id,
-            win_set,
-            numbers,
+            winning: parse_numbers(winning),
+            numbers: parse_numbers(numbers),
         }
     }
 
-    fn get_win_count(&self) -> u32 {
-        self.numbers
-            .iter()
-            .filter(|x| self.win_set.contains(x))
-            .count() as u32
+    fn get_win_count(&self) -> usize {
+        (self.winning & self.numbers).count_ones() as usize
     }
 
     fn get_points(&self) -> u32 {
         match self.get_win_count() {
             0 => 0,
-            count => 2_u32.pow(count - 1),
+            count => 1 << (count - 1),
         }
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(Card::new)
-            .map(|card| card.get_points())
-            .sum(),
-    )
+/// The number of instances of every card, where each instance wins a copy
+/// of the next `win_counts[i]` cards.
+///
+/// A special case of [`advent_of_code::propagate_forward`] whose edges span
+/// ranges, so the copies pending for the next cards are kept as a running
+/// total, and the copies expiring after each card in a difference array.
+fn cascade(win_counts: &[usize]) -> Vec<usize> {
+    let len = win_counts.len();
+    let mut expiring = vec![0; len + 1];
+    let mut pending = 0;
+    win_counts
+        .iter()
+        .enumerate()
+        .map(|(index, &count)| {
+            pending -= expiring[index];
+            let instances = 1 + pending;
+            pending += instances;
+            expiring[(index + 1 + count).min(len)] += instances;
+            instances
+        })
+        .collect()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let cards: Vec<Card> = input.lines().map(Card::new).collect();
-    let mut instances: HashMap<u32, u32> =
-        HashMap::from_iter(cards.iter().map(|card| (card.id, 1)));
-
-    cards.iter().for_each(|card| {
-        let count = card.get_win_count();
-        (1..count + 1).for_each(|i| {
-            let copies = instances[&card.id];
-            if let Some(counter) = instances.get_mut(&(card.id + i)) {
-                *counter += copies;
-            }
-        });
-    });
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Vec<Card>;
+    type PartOne = u32;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Vec<Card> {
+        input.lines().map(Card::new).collect()
+    }
 
-    Some(instances.values().sum())
+    fn part_one(cards: &Vec<Card>) -> Option<u32> {
+        Some(cards.iter().map(Card::get_points).sum())
+    }
+
+    fn part_two(cards: &Vec<Card>) -> Option<usize> {
+        let win_counts: Vec<usize> = cards.iter().map(Card::get_win_count).collect();
+        Some(cascade(&win_counts).into_iter().sum())
+    }
 }
 
 #[cfg(test)]
@@ -99,4 +102,18 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(30));
     }
+
+    #[test]
+    fn test_cascade() {
+        let win_counts = [4, 2, 2, 1, 0, 0];
+        assert_eq!(cascade(&win_counts), vec![1, 2, 4, 8, 14, 1]);
+
+        // winning past the last card wins nothing
+        let win_counts = [3, 5, 1, 0];
+        let last = win_counts.len() - 1;
+        let copies = advent_of_code::propagate_forward(vec![1; win_counts.len()], |card| {
+            card + 1..=(card + win_counts[card]).min(last)
+        });
+        assert_eq!(Some(cascade(&win_counts)), copies);
+    }
 }
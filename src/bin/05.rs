@@ -1,3 +1,4 @@
+use advent_of_code::Puzzle;
 use itertools::Itertools;
 use std::ops::Range;
 
@@ -8,11 +9,6 @@ struct Rule {
     source_range: Range<u64>,
 }
 
-struct MapRange {
-    mapped: Vec<Range<u64>>,
-    non_mapped: Vec<Range<u64>>,
-}
-
 impl Rule {
     fn new(rule_text: &str) -> Self {
         let mut iter = rule_text
@@ -27,124 +23,173 @@ impl Rule {
         }
     }
 
-    fn map(&self, x: u64) -> Option<u64> {
-        match x {
-            x if self.source_range.contains(&x) => {
-                Some(x - self.source_range.start + self.dest_start)
-            }
-            _ => None,
-        }
+    fn offset(&self) -> i64 {
+        self.dest_start as i64 - self.source_range.start as i64
     }
+}
 
-    fn map_range(&self, range: &Range<u64>) -> MapRange {
-        let Range { start, end } = *range;
-        let Range {
-            start: source_start,
-            end: source_end,
-        } = self.source_range;
-        match (
-            start < source_start && source_start < end,
-            start < source_end && source_end < end,
-        ) {
-            (true, true) => MapRange {
-                mapped: vec![Range {
-                    start: self.map(source_start).unwrap(),
-                    end: self.map(source_end - 1).unwrap() + 1,
-                }],
-                non_mapped: vec![
-                    Range {
-                        start,
-                        end: source_start,
-                    },
-                    Range {
-                        start: source_end,
-                        end,
-                    },
-                ],
-            },
-            (true, false) => MapRange {
-                mapped: vec![Range {
-                    start: self.map(source_start).unwrap(),
-                    end: self.map(end - 1).unwrap() + 1,
-                }],
-                non_mapped: vec![Range {
-                    start,
-                    end: source_start,
-                }],
-            },
-            (false, true) => MapRange {
-                mapped: vec![Range {
-                    start: self.map(start).unwrap(),
-                    end: self.map(source_end - 1).unwrap() + 1,
-                }],
-                non_mapped: vec![Range {
-                    start: source_end,
-                    end,
-                }],
-            },
-            (false, false) => {
-                if end <= source_start || start >= source_end {
-                    MapRange {
-                        mapped: vec![],
-                        non_mapped: vec![range.clone()],
-                    }
-                } else {
-                    MapRange {
-                        mapped: vec![Range {
-                            start: self.map(start).unwrap(),
-                            end: self.map(end - 1).unwrap() + 1,
-                        }],
-                        non_mapped: vec![],
-                    }
-                }
-            }
-        }
-    }
+/// Every value from `start` up to the start of the next piece is shifted by `offset`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Piece {
+    start: u64,
+    offset: i64,
 }
 
-struct Map {
-    rules: Vec<Rule>,
+/// A map over all of `u64` that is a shift on each of its pieces, so it is
+/// increasing between two breakpoints. The last piece reaches `u64::MAX`.
+#[derive(Clone, PartialEq, Debug)]
+struct PiecewiseMap {
+    pieces: Vec<Piece>,
 }
 
-impl Map {
-    fn new(map_text: &str) -> Self {
+impl PiecewiseMap {
+    fn identity() -> Self {
         Self {
-            rules: map_text.lines().map(Rule::new).collect(),
+            pieces: vec![Piece {
+                start: 0,
+                offset: 0,
+            }],
         }
     }
 
-    fn _map(&self, x: u64) -> Option<u64> {
-        use itertools::FoldWhile::{Continue, Done};
-        self.rules
+    /// Values no rule covers map to themselves.
+    fn new(rules: &[Rule]) -> Self {
+        let mut pieces = vec![];
+        let mut cursor = 0;
+        rules
             .iter()
-            .fold_while(None, |_, rule| match rule.map(x) {
-                Some(x) => Done(Some(x)),
-                None => Continue(None),
-            })
-            .into_inner()
+            .sorted_by_key(|rule| rule.source_range.start)
+            .for_each(|rule| {
+                if cursor < rule.source_range.start {
+                    pieces.push(Piece {
+                        start: cursor,
+                        offset: 0,
+                    });
+                }
+                pieces.push(Piece {
+                    start: rule.source_range.start,
+                    offset: rule.offset(),
+                });
+                cursor = rule.source_range.end;
+            });
+        pieces.push(Piece {
+            start: cursor,
+            offset: 0,
+        });
+        Self::from_pieces(pieces)
+    }
+
+    /// Sorts the pieces and drops those that are empty or do not change the offset.
+    fn from_pieces(mut pieces: Vec<Piece>) -> Self {
+        pieces.sort_by_key(|piece| piece.start);
+        let pieces = pieces
+            .into_iter()
+            .rev()
+            .dedup_by(|later, earlier| later.start == earlier.start)
+            .collect_vec()
+            .into_iter()
+            .rev()
+            .dedup_by(|later, earlier| later.offset == earlier.offset)
+            .collect();
+        Self { pieces }
+    }
+
+    /// The pieces with the values they cover.
+    fn ranges(&self) -> impl Iterator<Item = (Range<u64>, i64)> + '_ {
+        self.pieces.iter().enumerate().map(|(index, piece)| {
+            let end = self
+                .pieces
+                .get(index + 1)
+                .map_or(u64::MAX, |next| next.start);
+            (piece.start..end, piece.offset)
+        })
+    }
+
+    fn piece(&self, x: u64) -> &Piece {
+        &self.pieces[self.pieces.partition_point(|piece| piece.start <= x) - 1]
     }
 
     fn map(&self, x: u64) -> u64 {
-        self._map(x).unwrap_or(x)
+        x.checked_add_signed(self.piece(x).offset).unwrap()
     }
 
-    fn map_range(&self, range: &Range<u64>) -> Vec<Range<u64>> {
-        let (mut mapped, mut non_mapped) = (vec![], vec![range.clone()]);
-        self.rules.iter().for_each(|rule| {
-            let ranges: Vec<Range<u64>> = std::mem::take(&mut non_mapped);
-            ranges.iter().for_each(|range| {
-                let mut map_result = rule.map_range(range);
-                mapped.append(&mut map_result.mapped);
-                non_mapped.append(&mut map_result.non_mapped);
-            });
+    /// The breakpoints strictly inside `range`.
+    fn breakpoints(&self, range: &Range<u64>) -> impl Iterator<Item = u64> + '_ {
+        let Range { start, end } = range.clone();
+        self.pieces
+            .iter()
+            .map(|piece| piece.start)
+            .skip_while(move |&breakpoint| breakpoint <= start)
+            .take_while(move |&breakpoint| breakpoint < end)
+    }
+
+    /// The map applying `self` first and `next` to its result.
+    fn then(&self, next: &Self) -> Self {
+        let mut pieces = vec![];
+        self.ranges().for_each(|(range, offset)| {
+            let image =
+                range.start.saturating_add_signed(offset)..range.end.saturating_add_signed(offset);
+            [image.start]
+                .into_iter()
+                .chain(next.breakpoints(&image))
+                .for_each(|y| {
+                    pieces.push(Piece {
+                        start: y.saturating_add_signed(-offset),
+                        offset: offset + next.piece(y).offset,
+                    })
+                });
         });
-        [mapped, non_mapped].concat()
+        Self::from_pieces(pieces)
+    }
+
+    /// The inverse map, if every value is the image of exactly one value.
+    fn invert(&self) -> Option<Self> {
+        let images = self
+            .ranges()
+            .map(|(range, offset)| {
+                let image = range.start.checked_add_signed(offset)?
+                    ..range.end.saturating_add_signed(offset);
+                Some((image, -offset))
+            })
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .sorted_by_key(|(image, _)| image.start)
+            .collect_vec();
+
+        let tiles = images.first()?.0.start == 0
+            && images.last()?.0.end == u64::MAX
+            && images
+                .iter()
+                .tuple_windows()
+                .all(|((a, _), (b, _))| a.end == b.start);
+        tiles.then(|| {
+            Self::from_pieces(
+                images
+                    .into_iter()
+                    .map(|(image, offset)| Piece {
+                        start: image.start,
+                        offset,
+                    })
+                    .collect(),
+            )
+        })
+    }
+
+    /// The lowest value any of `ranges` maps to. The map only increases within
+    /// a piece, so it is enough to look at where ranges and pieces start.
+    fn min_over(&self, ranges: &[Range<u64>]) -> Option<u64> {
+        ranges
+            .iter()
+            .filter(|range| !range.is_empty())
+            .flat_map(|range| [range.start].into_iter().chain(self.breakpoints(range)))
+            .map(|x| self.map(x))
+            .min()
     }
 }
 
 struct Almanac {
     seeds: Vec<u64>,
-    maps: Vec<Map>,
+    maps: Vec<PiecewiseMap>,
 }
 
 impl Almanac {
@@ -158,47 +203,67 @@ impl Almanac {
     fn get_seeds(seeds_text: &str) -> Vec<u64> {
         seeds_text
             .split(':')
-            .last()
+            .next_back()
             .unwrap()
             .split_ascii_whitespace()
             .map(|x| x.parse().unwrap())
             .collect()
     }
 
-    fn get_map(map_text: &str) -> Map {
+    fn get_map(map_text: &str) -> PiecewiseMap {
         let map_text = map_text.split_once('\n').unwrap().1;
-        Map::new(map_text)
+        let rules: Vec<Rule> = map_text.lines().map(Rule::new).collect();
+        PiecewiseMap::new(&rules)
     }
 
-    fn get_lowest_location(&self, seeds: impl Iterator<Item = u64>) -> Option<u64> {
-        seeds
-            .map(|x| self.maps.iter().fold(x, |mapped_x, map| map.map(mapped_x)))
-            .min()
+    /// All maps composed into a single one.
+    fn seed_to_location(&self) -> PiecewiseMap {
+        self.maps
+            .iter()
+            .fold(PiecewiseMap::identity(), |composed, map| composed.then(map))
     }
 
-    fn map_range(&self, range: &Range<u64>) -> Vec<Range<u64>> {
-        self.maps.iter().fold(vec![range.clone()], |v, map| {
-            v.iter().flat_map(|range| map.map_range(range)).collect()
-        })
+    fn seed_ranges(&self) -> Vec<Range<u64>> {
+        self.seeds
+            .iter()
+            .tuples()
+            .map(|(&start, &range)| start..start + range)
+            .collect()
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let almanac = Almanac::new(input);
-    let iter = almanac.seeds.iter().copied();
-    almanac.get_lowest_location(iter)
-}
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Almanac;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Almanac {
+        Almanac::new(input)
+    }
+
+    fn part_one(almanac: &Almanac) -> Option<u64> {
+        let seed_to_location = almanac.seed_to_location();
+        almanac
+            .seeds
+            .iter()
+            .map(|&seed| seed_to_location.map(seed))
+            .min()
+    }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let almanac = Almanac::new(input);
-    almanac
-        .seeds
-        .iter()
-        .tuples()
-        .map(|(&start, &range)| start..start + range)
-        .flat_map(|range| almanac.map_range(&range))
-        .map(|range| range.start)
-        .min()
+    fn part_two(almanac: &Almanac) -> Option<u64> {
+        let seed_to_location = almanac.seed_to_location();
+        let seed_ranges = almanac.seed_ranges();
+        let location = seed_to_location.min_over(&seed_ranges)?;
+
+        debug_assert!(seed_to_location.invert().is_none_or(|location_to_seed| {
+            let seed = location_to_seed.map(location);
+            seed_ranges.iter().any(|range| range.contains(&seed))
+        }));
+
+        Some(location)
+    }
 }
 
 #[cfg(test)]
@@ -216,4 +281,41 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(46));
     }
+
+    #[test]
+    fn test_composition() {
+        let almanac = Almanac::new(&advent_of_code::template::read_file("examples", DAY));
+        let seed_to_location = almanac.seed_to_location();
+        let seeds = [79, 14, 55, 13];
+        let locations: Vec<u64> = seeds
+            .iter()
+            .map(|&seed| seed_to_location.map(seed))
+            .collect();
+        assert_eq!(locations, vec![82, 43, 86, 35]);
+
+        let stepwise = |seed| almanac.maps.iter().fold(seed, |x, map| map.map(x));
+        assert!((0..200).all(|seed| seed_to_location.map(seed) == stepwise(seed)));
+
+        let location_to_seed = seed_to_location.invert().unwrap();
+        assert!(locations
+            .iter()
+            .zip(seeds)
+            .all(|(&location, seed)| location_to_seed.map(location) == seed));
+        assert_eq!(
+            seed_to_location.then(&location_to_seed),
+            PiecewiseMap::identity()
+        );
+    }
+
+    #[test]
+    fn test_min_over() {
+        let map = PiecewiseMap::new(&[Rule::new("50 98 2"), Rule::new("52 50 48")]);
+        assert_eq!(map.min_over(&[90..95, 95..100]), Some(50));
+        assert_eq!(map.min_over(&[40..50, 55..60]), Some(40));
+        assert_eq!(map.min_over(&[60..98, 100..101]), Some(62));
+        assert_eq!(map.min_over(&[]), None);
+
+        let overlapping = PiecewiseMap::new(&[Rule::new("0 10 5")]);
+        assert_eq!(overlapping.invert(), None);
+    }
 }
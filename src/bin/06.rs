@@ -1,4 +1,5 @@
-use std::ops::Range;
+use advent_of_code::{quadratic_positive_range, Puzzle};
+use std::ops::RangeInclusive;
 
 advent_of_code::solution!(6);
 
@@ -7,20 +8,17 @@ struct Race {
     distance: u64,
 }
 
-const TOLERANCE: f64 = 1e-10;
-
 impl Race {
-    fn win(&self) -> Option<Range<u64>> {
-        let time = self.time as f64;
-        let distance = self.distance as f64;
-        let delta = time.powi(2) - 4. * distance;
-        if delta > TOLERANCE {
-            let start = ((time - delta.sqrt()) / 2. + 0.5).round() as u64;
-            let end = ((time + delta.sqrt()) / 2. - 0.6).round() as u64 + 1;
-            let range = Range { start, end };
-            return Some(range);
-        }
-        None
+    /// The hold times that beat the record: holding for `x` travels
+    /// `x·(time - x)`, so they are where `-x² + time·x - distance > 0`.
+    fn win(&self) -> Option<RangeInclusive<u64>> {
+        let range = quadratic_positive_range(-1, self.time.into(), -i128::from(self.distance))?;
+        Some(*range.start() as u64..=*range.end() as u64)
+    }
+
+    fn ways_to_win(&self) -> u64 {
+        self.win()
+            .map_or(0, |range| range.end() - range.start() + 1)
     }
 }
 
@@ -28,7 +26,7 @@ type Game = Vec<Race>;
 
 fn parse_line(line: &str) -> impl Iterator<Item = u64> + '_ {
     line.split(':')
-        .last()
+        .next_back()
         .unwrap()
         .split_ascii_whitespace()
         .map(|num| num.parse().unwrap())
@@ -45,16 +43,9 @@ fn parse_game(game_text: &str) -> Game {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    parse_game(input)
-        .iter()
-        .map(|race| race.win().map(|range| range.count() as u64))
-        .product()
-}
-
 fn parse_number(line: &str) -> u64 {
     line.split(':')
-        .last()
+        .next_back()
         .unwrap()
         .split_ascii_whitespace()
         .collect::<String>()
@@ -62,13 +53,36 @@ fn parse_number(line: &str) -> u64 {
         .unwrap()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut lines = input.lines();
+/// The single race the sheet describes once the spaces between digits are
+/// ignored.
+fn parse_kerned_race(game_text: &str) -> Race {
+    let mut lines = game_text.lines();
     let time = parse_number(lines.next().unwrap());
     let distance = parse_number(lines.next().unwrap());
     Race { time, distance }
-        .win()
-        .map(|range| range.count() as u64)
+}
+
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = (Game, Race);
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> (Game, Race) {
+        (parse_game(input), parse_kerned_race(input))
+    }
+
+    fn part_one((game, _): &(Game, Race)) -> Option<u64> {
+        game.iter()
+            .map(|race| race.ways_to_win())
+            .product::<u64>()
+            .into()
+    }
+
+    fn part_two((_, race): &(Game, Race)) -> Option<u64> {
+        Some(race.ways_to_win())
+    }
 }
 
 #[cfg(test)]
@@ -86,4 +100,37 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(71503));
     }
+
+    #[test]
+    fn test_large_race() {
+        let brute_force = |race: &Race| {
+            (0..=race.time)
+                .filter(|hold| hold * (race.time - hold) > race.distance)
+                .count() as u64
+        };
+        [(7, 9), (30, 200), (71530, 940200), (10, 25), (10, 24)]
+            .into_iter()
+            .map(|(time, distance)| Race { time, distance })
+            .for_each(|race| assert_eq!(race.ways_to_win(), brute_force(&race)));
+
+        // the records lie right below the peak, closer than f64 can resolve
+        let race = Race {
+            time: 4_000_000_000,
+            distance: 3_999_999_999_999_999_999,
+        };
+        assert_eq!(race.win(), Some(2_000_000_000..=2_000_000_000));
+        let race = Race {
+            distance: 4_000_000_000_000_000_000,
+            ..race
+        };
+        assert_eq!(race.win(), None);
+
+        // the squared time no longer fits in i128
+        let race = Race {
+            time: u64::MAX,
+            distance: u64::MAX - 1,
+        };
+        assert_eq!(race.win(), Some(2..=u64::MAX - 2));
+        assert_eq!(race.ways_to_win(), u64::MAX - 3);
+    }
 }
@@ -1,239 +1,131 @@
+use advent_of_code::Puzzle;
 use itertools::Itertools;
-use std::{
-    cmp::Ordering,
-    ops::{Index, IndexMut},
-    slice::Iter,
-};
 
 advent_of_code::solution!(7);
 
-#[derive(PartialEq, Eq)]
-struct Hand([char; 5]);
-
-impl Index<usize> for Hand {
-    type Output = char;
-    fn index(&self, index: usize) -> &Self::Output {
-        self.0.index(index)
-    }
+/// How wildcards count when comparing hands.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum WildcardRule {
+    /// Wildcards join the most common other label to make the best hand type,
+    /// but keep their own rank when breaking ties.
+    BestSubstitution,
+    /// Like `BestSubstitution`, but wildcards are weaker than every other
+    /// label when breaking ties.
+    LowestRank,
 }
 
-impl IndexMut<usize> for Hand {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        self.0.index_mut(index)
-    }
+struct Rules {
+    /// Labels from weakest to strongest.
+    labels: Vec<char>,
+    hand_size: usize,
+    wildcards: Vec<char>,
+    wildcard_rule: WildcardRule,
 }
 
-impl Hand {
-    fn iter(&self) -> Iter<'_, char> {
-        self.0.iter()
-    }
-
-    fn map<F>(&self, f: F) -> Hand
-    where
-        F: FnMut(char) -> char,
-    {
-        Hand(self.0.map(f))
-    }
-}
+/// The label counts of a hand from most to least common; comparing them
+/// lexicographically orders five of a kind above four of a kind and so on.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct HandType(Vec<usize>);
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
-enum HandType {
-    High,
-    OnePair,
-    TwoPair,
-    ThreeKind,
-    FullHouse,
-    FourKind,
-    FiveKind,
-}
-
-#[derive(Eq)]
 struct CamelCard {
-    hand: Hand,
     hand_type: HandType,
-    map: [(u32, char); 5],
+    ranks: Vec<usize>,
     bid: u32,
 }
 
-const JOKER: char = 'J';
-const MAX_LABEL: char = 'A';
-const MIN_LABEL: char = 'X';
-const EMPTY: char = ' ';
-
-fn label_to_rank(label: &char) -> Option<u32> {
-    match *label {
-        MAX_LABEL => Some(14),
-        'K' => Some(13),
-        'Q' => Some(12),
-        JOKER => Some(11),
-        'T' => Some(10),
-        '9' => Some(9),
-        '8' => Some(8),
-        '7' => Some(7),
-        '6' => Some(6),
-        '5' => Some(5),
-        '4' => Some(4),
-        '3' => Some(3),
-        '2' => Some(2),
-        MIN_LABEL => Some(1),
-        _ => None,
-    }
-}
-
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl Rules {
+    fn standard() -> Self {
+        Self {
+            labels: "23456789TJQKA".chars().collect(),
+            hand_size: 5,
+            wildcards: vec![],
+            wildcard_rule: WildcardRule::BestSubstitution,
+        }
     }
-}
 
-impl Ord for Hand {
-    fn cmp(&self, other: &Self) -> Ordering {
-        for index in 0..5 {
-            let self_rank = label_to_rank(&self.0[index]).unwrap();
-            let other_rank = label_to_rank(&other.0[index]).unwrap();
-            match self_rank.cmp(&other_rank) {
-                Ordering::Greater => return Ordering::Greater,
-                Ordering::Less => return Ordering::Less,
-                _ => continue,
-            }
+    fn jokers() -> Self {
+        Self {
+            wildcards: vec!['J'],
+            wildcard_rule: WildcardRule::LowestRank,
+            ..Self::standard()
         }
-        Ordering::Equal
     }
-}
 
-impl CamelCard {
-    fn new(camel_card: &str) -> Self {
-        let mut iter = camel_card.split_ascii_whitespace();
-        let mut hand = Hand([EMPTY; 5]);
-        iter.next()
-            .unwrap()
-            .chars()
-            .enumerate()
-            .for_each(|(i, c)| hand[i] = c);
-        let bid = iter.next().unwrap().parse().unwrap();
-        Self::from(hand, bid)
+    fn is_wildcard(&self, label: &char) -> bool {
+        self.wildcards.contains(label)
     }
 
-    fn from(hand: Hand, bid: u32) -> Self {
-        let map = Self::get_count_map(&hand);
-        let hand_type = Self::hand_type(&map);
-        Self {
-            hand,
-            hand_type,
-            map,
-            bid,
+    fn rank(&self, label: &char) -> usize {
+        if self.wildcard_rule == WildcardRule::LowestRank && self.is_wildcard(label) {
+            return 0;
+        }
+        match self.labels.iter().position(|l| l == label) {
+            Some(index) => index + 1,
+            None => panic!("Could not resolve the label."),
         }
     }
 
-    fn get_count_map(hand: &Hand) -> [(u32, char); 5] {
-        let mut map = [(0, EMPTY); 5];
-        hand.iter()
-            .counts()
+    fn hand_type(&self, hand: &[char]) -> HandType {
+        let wildcards = hand.iter().filter(|label| self.is_wildcard(label)).count();
+        let mut counts: Vec<usize> = hand
             .iter()
-            .map(|(&ch, count)| (*count as u32, *ch))
-            .sorted_by(|a, b| match a.0.cmp(&b.0) {
-                Ordering::Equal => label_to_rank(&a.1).cmp(&label_to_rank(&b.1)),
-                order => order,
-            })
+            .filter(|label| !self.is_wildcard(label))
+            .counts()
+            .into_values()
+            .sorted()
             .rev()
-            .enumerate()
-            .for_each(|(i, pair)| map[i] = pair);
-        map
-    }
+            .collect();
 
-    fn hand_type(map: &[(u32, char); 5]) -> HandType {
-        let mut count_arr = [0, 0, 0, 0, 0];
-        map.iter()
-            .map(|pair| pair.0)
-            .enumerate()
-            .for_each(|(i, count)| count_arr[i] = count);
-        match count_arr {
-            [5, 0, 0, 0, 0] => HandType::FiveKind,
-            [4, 1, 0, 0, 0] => HandType::FourKind,
-            [3, 2, 0, 0, 0] => HandType::FullHouse,
-            [3, 1, 1, 0, 0] => HandType::ThreeKind,
-            [2, 2, 1, 0, 0] => HandType::TwoPair,
-            [2, 1, 1, 1, 0] => HandType::OnePair,
-            _ => HandType::High,
+        match counts.first_mut() {
+            Some(most) => *most += wildcards,
+            None => counts.push(wildcards),
         }
+        HandType(counts)
     }
 
-    fn map_x_to_y(hand: &Hand, x: char, y: char) -> Hand {
-        hand.map(|c| if c == x { y } else { c })
-    }
+    fn card(&self, camel_card: &str) -> CamelCard {
+        let mut iter = camel_card.split_ascii_whitespace();
+        let hand: Vec<char> = iter.next().unwrap().chars().collect();
+        assert_eq!(hand.len(), self.hand_size, "Unexpected hand size.");
+        let bid = iter.next().unwrap().parse().unwrap();
 
-    fn strongest(&self) -> Self {
-        let Self {
-            hand,
-            hand_type: _,
-            map,
+        CamelCard {
+            hand_type: self.hand_type(&hand),
+            ranks: hand.iter().map(|label| self.rank(label)).collect(),
             bid,
-        } = self;
-
-        let map_to_char = map
-            .iter()
-            .map(|(_, c)| *c)
-            .find(|&c| c != 'J' && c != EMPTY)
-            .unwrap_or(MAX_LABEL);
-        let strongest_hand = Self::map_x_to_y(hand, JOKER, map_to_char);
-        let hand_type = Self::from(strongest_hand, *bid).hand_type;
-
-        Self {
-            hand: Self::map_x_to_y(hand, JOKER, MIN_LABEL),
-            hand_type,
-            map: *map,
-            bid: self.bid,
         }
     }
-}
 
-impl PartialEq for CamelCard {
-    fn eq(&self, other: &Self) -> bool {
-        match self.hand_type.eq(&other.hand_type) {
-            true => true,
-            false => self.hand.eq(&other.hand),
-        }
+    fn total_winnings(&self, lines: &[&str]) -> u32 {
+        lines
+            .iter()
+            .map(|line| self.card(line))
+            .sorted()
+            .enumerate()
+            .map(|(rank, card)| (rank as u32 + 1) * card.bid)
+            .sum()
     }
 }
 
-impl PartialOrd for CamelCard {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+struct Solution;
 
-impl Ord for CamelCard {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match self.hand_type.cmp(&other.hand_type) {
-            Ordering::Equal => self.hand.cmp(&other.hand),
-            other => other,
-        }
+impl Puzzle for Solution {
+    type Parsed<'a> = Vec<&'a str>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        input.lines().collect()
     }
-}
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(CamelCard::new)
-            .sorted()
-            .enumerate()
-            .map(|(rank, card)| (rank as u32 + 1) * card.bid)
-            .sum(),
-    )
-}
+    fn part_one(lines: &Self::Parsed<'_>) -> Option<u32> {
+        Some(Rules::standard().total_winnings(lines))
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(CamelCard::new)
-            .map(|card| card.strongest())
-            .sorted()
-            .enumerate()
-            .map(|(rank, card)| (rank as u32 + 1) * card.bid)
-            .sum(),
-    )
+    fn part_two(lines: &Self::Parsed<'_>) -> Option<u32> {
+        Some(Rules::jokers().total_winnings(lines))
+    }
 }
 
 #[cfg(test)]
@@ -251,4 +143,42 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(5905));
     }
+
+    #[test]
+    fn test_variant_rules() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let lines = Solution::parse(&input);
+
+        let rules = Rules {
+            wildcard_rule: WildcardRule::BestSubstitution,
+            ..Rules::jokers()
+        };
+        assert_eq!(rules.total_winnings(&lines), 5905);
+
+        // both are four of a kind, so the joker's own rank breaks the tie
+        let lines = Solution::parse("JKKK2 1\nTTTT2 10");
+        assert_eq!(Rules::jokers().total_winnings(&lines), 1 + 10 * 2);
+        assert_eq!(rules.total_winnings(&lines), 10 + 2);
+        let lines = Solution::parse(&input);
+
+        let rules = Rules {
+            wildcards: vec!['J', 'T'],
+            ..Rules::jokers()
+        };
+        assert_eq!(rules.total_winnings(&lines), 6843);
+    }
+
+    #[test]
+    fn test_hand_size() {
+        let rules = Rules {
+            labels: "ABC".chars().collect(),
+            hand_size: 3,
+            wildcards: vec!['A'],
+            wildcard_rule: WildcardRule::LowestRank,
+        };
+        assert_eq!(rules.hand_type(&['A', 'A', 'A']), HandType(vec![3]));
+        assert_eq!(rules.hand_type(&['A', 'B', 'C']), HandType(vec![2, 1]));
+        assert!(rules.card("ACC 1") > rules.card("BBC 1"));
+        assert!(rules.card("BBB 1") > rules.card("ABB 1"));
+    }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::Puzzle;
 use num::integer::lcm;
 use std::collections::HashMap;
 
@@ -126,12 +127,24 @@ impl Navigate {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    Some(Navigate::new(input).navigate_to_end(&START, |node| *node == END))
-}
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Navigate;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Navigate {
+        Navigate::new(input)
+    }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    Some(Navigate::new(input).navigate_all_to_end_by_ghost())
+    fn part_one(navigate: &Navigate) -> Option<u64> {
+        Some(navigate.navigate_to_end(&START, |node| *node == END))
+    }
+
+    fn part_two(navigate: &Navigate) -> Option<u64> {
+        Some(navigate.navigate_all_to_end_by_ghost())
+    }
 }
 
 #[cfg(test)]
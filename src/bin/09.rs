@@ -1,3 +1,5 @@
+use advent_of_code::{newton_backward, newton_forward, Puzzle};
+
 advent_of_code::solution!(9);
 
 struct History(Vec<i32>);
@@ -13,34 +15,12 @@ impl History {
     }
 
     fn extrapolate(&self) -> i32 {
-        let mut values = self.0.clone();
-        let mut result = 0;
-
-        while !values.iter().all(|x| *x == 0) {
-            for i in 0..values.len() - 1 {
-                values[i] = values[i + 1] - values[i];
-            }
-            result += values.pop().unwrap();
-        }
-
-        result
+        let next = self.0.len() as i32;
+        newton_backward(&self.0, next).expect("The prediction should fit in i32.")
     }
 
     fn extrapolate_backward(&self) -> i32 {
-        let mut values = self.0.clone();
-        let mut sign = 1;
-        let mut result = 0;
-
-        while !values.iter().all(|x| *x == 0) {
-            result += sign * values.first().unwrap();
-            sign *= -1;
-            for i in 0..values.len() - 1 {
-                values[i] = values[i + 1] - values[i];
-            }
-            values.pop();
-        }
-
-        result
+        newton_forward(&self.0, -1).expect("The prediction should fit in i32.")
     }
 }
 
@@ -48,17 +28,24 @@ fn parse_histories(histories: &str) -> Vec<History> {
     histories.lines().map(History::new).collect()
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
-    Some(parse_histories(input).iter().map(|h| h.extrapolate()).sum())
-}
+struct Solution;
 
-pub fn part_two(input: &str) -> Option<i32> {
-    Some(
+impl Puzzle for Solution {
+    type Parsed<'a> = Vec<History>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(input: &str) -> Vec<History> {
         parse_histories(input)
-            .iter()
-            .map(|h| h.extrapolate_backward())
-            .sum(),
-    )
+    }
+
+    fn part_one(histories: &Vec<History>) -> Option<i32> {
+        Some(histories.iter().map(|h| h.extrapolate()).sum())
+    }
+
+    fn part_two(histories: &Vec<History>) -> Option<i32> {
+        Some(histories.iter().map(|h| h.extrapolate_backward()).sum())
+    }
 }
 
 #[cfg(test)]
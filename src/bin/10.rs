@@ -1,9 +1,15 @@
-use advent_of_code::{number_of_interiors, Matrix, Position as P};
+use advent_of_code::{
+    classify_regions, number_of_interiors,
+    visualize::{Color, Frame, Glyph, Recorder, Style},
+    Direction, Matrix, Movable, Position as P, Puzzle, Region,
+};
+use itertools::Itertools;
+use std::io;
 type Position = P<usize>;
 
 advent_of_code::solution!(10);
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 enum Pipe {
     NorthSouth,
     EastWest,
@@ -21,6 +27,7 @@ enum Tile {
 }
 
 use self::Pipe::*;
+use Direction::*;
 use Tile::*;
 
 impl Tile {
@@ -39,131 +46,218 @@ impl Tile {
     }
 }
 
-struct Grid {
-    tiles: Matrix<Tile>,
-    start: Position,
-}
-
-impl Grid {
-    fn new(grid: &str) -> Self {
-        let (mut row, mut col) = (0, 0);
-        let mut start_position = None;
-        let mut tiles = vec![];
-        grid.lines().for_each(|line| {
-            row += 1;
-            col = 0;
-            let mut row_tiles = vec![];
-            line.chars().for_each(|ch| {
-                col += 1;
-                let tile = Tile::new(&ch).unwrap();
-                if start_position.is_none() && tile == Start {
-                    start_position = Some((row, col).into());
-                }
-                row_tiles.push(tile);
-            });
-            tiles.push(row_tiles);
-        });
+const PIPES: [Pipe; 6] = [
+    NorthSouth, EastWest, NorthEast, NorthWest, SouthWest, SouthEast,
+];
 
-        Self {
-            tiles: tiles.into(),
-            start: start_position.unwrap(),
+impl Pipe {
+    fn connections(&self) -> [Direction; 2] {
+        match self {
+            NorthSouth => [Up, Down],
+            EastWest => [Right, Left],
+            NorthEast => [Up, Right],
+            NorthWest => [Up, Left],
+            SouthWest => [Down, Left],
+            SouthEast => [Down, Right],
         }
     }
 
-    fn get_tile(&self, pos: &Position) -> &Tile {
-        &self.tiles[*pos]
+    fn is_connected_to(&self, direction: &Direction) -> bool {
+        self.connections().contains(direction)
     }
 
-    fn get_pipe_adjacent_positions(&self, pos: &Position) -> Vec<Position> {
-        let Position { row, col } = *pos;
-        let tile = self.get_tile(pos);
+    fn from_connections(a: &Direction, b: &Direction) -> Option<Self> {
+        PIPES
+            .into_iter()
+            .find(|pipe| a != b && pipe.is_connected_to(a) && pipe.is_connected_to(b))
+    }
 
-        let positions = match tile {
-            Tile::Pipe(pipe) => match pipe {
-                NorthSouth => vec![(row - 1, col), (row + 1, col)],
-                EastWest => vec![(row, col - 1), (row, col + 1)],
-                NorthEast => vec![(row - 1, col), (row, col + 1)],
-                NorthWest => vec![(row - 1, col), (row, col - 1)],
-                SouthWest => vec![(row + 1, col), (row, col - 1)],
-                SouthEast => vec![(row + 1, col), (row, col + 1)],
-            },
-            Ground => vec![],
-            Start => vec![
-                (row - 1, col),
-                (row + 1, col),
-                (row, col - 1),
-                (row, col + 1),
-            ],
+    /// The direction a walker leaves this pipe in after entering it heading `direction`.
+    fn exit(&self, direction: &Direction) -> Direction {
+        let entry = direction.turn_back();
+        let [a, b] = self.connections();
+        if a == entry {
+            b
+        } else {
+            a
         }
-        .into_iter()
-        .map(Position::from);
-
-        positions
-            .into_iter()
-            .filter(|pos| self.tiles.is_valid_position(pos))
-            .collect()
     }
 
-    fn is_connect(&self, pos: &Position, other_pos: &Position) -> bool {
-        assert!(pos != other_pos, "There are same position.");
-        let adjacent_positions = self.get_pipe_adjacent_positions(pos);
-        let other_adjacent_positions = self.get_pipe_adjacent_positions(other_pos);
-        if adjacent_positions.contains(other_pos) && other_adjacent_positions.contains(pos) {
-            return true;
+    fn symbol(&self) -> char {
+        match self {
+            NorthSouth => '│',
+            EastWest => '─',
+            NorthEast => '└',
+            NorthWest => '┘',
+            SouthWest => '┐',
+            SouthEast => '┌',
         }
-        false
     }
+}
+
+struct Grid {
+    tiles: Matrix<Tile>,
+    start_pipe: Pipe,
+    /// The giant loop in walking order, starting at the start tile; every
+    /// step faces the direction it leaves its tile in.
+    giant_loop: Vec<Movable<usize>>,
+}
 
-    fn get_connected_pipe_positions(&self, pos: &Position) -> Vec<Position> {
-        self.get_pipe_adjacent_positions(pos)
+impl Grid {
+    fn new(grid: &str) -> Option<Self> {
+        let tiles: Vec<Vec<Tile>> = grid
+            .lines()
+            .map(|line| line.chars().map(|ch| Tile::new(&ch)).collect())
+            .collect::<Option<_>>()?;
+        let tiles: Matrix<Tile> = tiles.into();
+        let start = tiles
+            .indexes()
             .into_iter()
-            .filter(|adjacent_pos| self.is_connect(pos, adjacent_pos))
-            .collect()
+            .find(|position| tiles[*position] == Start)?;
+        let (start_pipe, giant_loop) = Self::infer_start_pipe(&tiles, &start)?;
+
+        Some(Self {
+            tiles,
+            start_pipe,
+            giant_loop,
+        })
     }
 
-    fn get_giant_loop(&self) -> Vec<Position> {
-        let mut giant_loop = vec![];
+    /// The pipe hidden under the start tile, and the loop through it. Every
+    /// pair of neighbors whose pipes lead back to the start is tried, as
+    /// stray pipes may point at it too.
+    fn infer_start_pipe(
+        tiles: &Matrix<Tile>,
+        start: &Position,
+    ) -> Option<(Pipe, Vec<Movable<usize>>)> {
+        [Up, Down, Left, Right]
+            .into_iter()
+            .filter(|direction| {
+                neighbor(tiles, start, direction).is_some_and(|position| match &tiles[position] {
+                    Tile::Pipe(pipe) => pipe.is_connected_to(&direction.turn_back()),
+                    _ => false,
+                })
+            })
+            .tuple_combinations()
+            .find_map(|(a, b)| {
+                let pipe = Pipe::from_connections(&a, &b)?;
+                Some((pipe, walk_loop(tiles, start, &pipe)?))
+            })
+    }
 
-        for pos in self.get_connected_pipe_positions(&self.start) {
-            giant_loop.clear();
-            let mut current_pos = self.start;
-            let mut next = Some(pos);
-            while let Some(next_pos) = next {
-                let prev_pos = current_pos;
-                current_pos = next_pos;
+    fn get_pipe(&self, pos: &Position) -> Pipe {
+        match &self.tiles[*pos] {
+            Tile::Pipe(pipe) => *pipe,
+            Start => self.start_pipe,
+            Ground => panic!("The loop should not reach the ground."),
+        }
+    }
 
-                if current_pos == self.start {
-                    break;
-                }
-                giant_loop.push(current_pos);
+    fn get_giant_loop(&self) -> &[Movable<usize>] {
+        &self.giant_loop
+    }
 
-                next = self
-                    .get_connected_pipe_positions(&current_pos)
-                    .into_iter()
-                    .find(|pos| *pos != prev_pos);
-            }
-        }
+    fn get_loop_positions(&self) -> Vec<Position> {
+        self.get_giant_loop()
+            .iter()
+            .map(|step| step.position)
+            .collect()
+    }
 
-        giant_loop.insert(0, self.start);
-        giant_loop
+    fn get_regions(&self) -> Matrix<Region> {
+        classify_regions(self.tiles.rows, self.tiles.cols, &self.get_loop_positions())
     }
 
     fn get_number_of_interior_points(&self) -> usize {
         let circle = self
-            .get_giant_loop()
+            .get_loop_positions()
             .iter()
             .map(|p| P::<isize>::new(p.row as isize, p.col as isize))
             .collect::<Vec<P<isize>>>();
         number_of_interiors(&circle)
     }
+
+    fn render(&self, regions: &Matrix<Region>) -> Frame {
+        Frame::new(&self.tiles, |position, tile| match regions[position] {
+            Region::Boundary => self.get_pipe(&position).symbol().into(),
+            Region::Inside => Glyph {
+                symbol: 'I',
+                style: Style::foreground(Color::GREEN),
+            },
+            Region::Outside => Glyph {
+                symbol: if *tile == Ground { 'O' } else { '·' },
+                style: Style::foreground(Color::GRAY),
+            },
+        })
+    }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(Grid::new(input).get_giant_loop().len() / 2)
+fn neighbor(tiles: &Matrix<Tile>, pos: &Position, direction: &Direction) -> Option<Position> {
+    let mut neighbor = *pos;
+    neighbor.move_to(direction, 1);
+    tiles.is_valid_position(&neighbor).then_some(neighbor)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    Some(Grid::new(input).get_number_of_interior_points())
+/// Follows the pipes from `start`, with `start_pipe` under it, until the walk
+/// comes back, or `None` if it runs into anything that does not connect.
+fn walk_loop(
+    tiles: &Matrix<Tile>,
+    start: &Position,
+    start_pipe: &Pipe,
+) -> Option<Vec<Movable<usize>>> {
+    let mut giant_loop = vec![];
+    let mut walker = Movable::new(*start, start_pipe.connections()[0]);
+
+    loop {
+        giant_loop.push(walker);
+        walker.position = neighbor(tiles, &walker.position, &walker.direction)?;
+        let entry = walker.direction.turn_back();
+        let pipe = match &tiles[walker.position] {
+            Start => {
+                return start_pipe.is_connected_to(&entry).then_some(giant_loop);
+            }
+            Tile::Pipe(pipe) if pipe.is_connected_to(&entry) => pipe,
+            _ => return None,
+        };
+        walker.turn_to(&pipe.exit(&walker.direction));
+    }
+}
+
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Option<Grid>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Option<Grid> {
+        Grid::new(input)
+    }
+
+    fn part_one(grid: &Option<Grid>) -> Option<usize> {
+        Some(grid.as_ref()?.get_giant_loop().len() / 2)
+    }
+
+    fn part_two(grid: &Option<Grid>) -> Option<usize> {
+        let grid = grid.as_ref()?;
+        let inside = grid
+            .get_regions()
+            .iter()
+            .filter(|&&region| region == Region::Inside)
+            .count();
+        debug_assert_eq!(inside, grid.get_number_of_interior_points());
+
+        Some(inside)
+    }
+
+    fn export(grid: &Option<Grid>) -> io::Result<()> {
+        let Some(grid) = grid else {
+            return Ok(());
+        };
+        let mut recorder = Recorder::from_env();
+        recorder.record(|| grid.render(&grid.get_regions()));
+        recorder.save("10")
+    }
 }
 
 #[cfg(test)]
@@ -195,4 +289,41 @@ mod tests {
         ));
         assert_eq!(result, Some(10));
     }
+
+    #[test]
+    fn test_start_pipe() {
+        let grid = Grid::new(&advent_of_code::template::read_file_part(
+            "examples", DAY, 1,
+        ))
+        .unwrap();
+        assert_eq!(grid.start_pipe, SouthEast);
+
+        let grid = Grid::new(&advent_of_code::template::read_file_part(
+            "examples", DAY, 4,
+        ))
+        .unwrap();
+        assert_eq!(grid.start_pipe, SouthWest);
+
+        let grid = Grid::new(".|...\n.S-7.\n.|.|.\n.L-J.\n.....").unwrap();
+        assert_eq!(grid.start_pipe, SouthEast);
+        assert_eq!(grid.get_giant_loop().len(), 8);
+
+        assert!(Grid::new("S-7\n|.|\n|-J").is_none());
+    }
+
+    #[test]
+    fn test_interiors_match_picks_theorem() {
+        (1..=4).for_each(|part| {
+            let grid = Grid::new(&advent_of_code::template::read_file_part(
+                "examples", DAY, part,
+            ))
+            .unwrap();
+            let inside = grid
+                .get_regions()
+                .iter()
+                .filter(|&&region| region == Region::Inside)
+                .count();
+            assert_eq!(inside, grid.get_number_of_interior_points());
+        });
+    }
 }
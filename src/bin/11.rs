@@ -1,4 +1,4 @@
-use advent_of_code::Position as P;
+use advent_of_code::{Position as P, PrefixSum, Puzzle};
 use std::collections::HashSet;
 type Position = P<usize>;
 
@@ -26,6 +26,8 @@ struct Image {
     galaxies: Vec<Position>,
     rows: HashSet<usize>,
     cols: HashSet<usize>,
+    height: usize,
+    width: usize,
 }
 
 impl Image {
@@ -52,62 +54,64 @@ impl Image {
             galaxies,
             rows: galaxy_rows,
             cols: galaxy_cols,
+            height: row,
+            width: col,
         }
     }
 
-    fn get_shortest_path_length(
-        &self,
-        galaxy: &Position,
-        other_galaxy: &Position,
-        expansion: usize,
-    ) -> usize {
-        let Position {
-            row: galaxy_row,
-            col: galaxy_col,
-        } = *galaxy;
-        let Position {
-            row: other_galaxy_row,
-            col: other_galaxy_col,
-        } = *other_galaxy;
-
-        let mut steps = 0;
-
-        let get_steps = |a: usize, b: usize, should_expand: &dyn Fn(&usize) -> bool| {
-            let min = Ord::min(a, b);
-            let max = Ord::max(a, b);
-            (min + 1..=max)
-                .map(|row| if should_expand(&row) { 1 } else { expansion })
-                .sum::<usize>()
-        };
-
-        steps += get_steps(galaxy_row, other_galaxy_row, &|row| self.rows.contains(row));
-        steps += get_steps(galaxy_col, other_galaxy_col, &|col| self.cols.contains(col));
-
-        steps
+    /// The sum of the shortest paths between all pairs of galaxies, after
+    /// every empty row grows `row_expansion` times and every empty column
+    /// `col_expansion` times.
+    fn distance_sum(&self, row_expansion: usize, col_expansion: usize) -> usize {
+        let rows = expanded_offsets(self.height, &self.rows, row_expansion);
+        let cols = expanded_offsets(self.width, &self.cols, col_expansion);
+
+        pairwise_distance_sum(self.galaxies.iter().map(|galaxy| rows.prefix(galaxy.row)))
+            + pairwise_distance_sum(self.galaxies.iter().map(|galaxy| cols.prefix(galaxy.col)))
     }
 }
 
-fn solve(input: &str, expansion: usize) -> Option<usize> {
-    let image = Image::new(input);
-    let galaxies = &image.galaxies;
-    let galaxies_len = galaxies.len();
-    Some(
-        (0..galaxies_len - 1)
-            .map(|i| {
-                (i + 1..galaxies_len)
-                    .map(|j| image.get_shortest_path_length(&galaxies[i], &galaxies[j], expansion))
-                    .sum::<usize>()
-            })
-            .sum(),
-    )
+/// Cumulative sizes of the lines `1..=len`, so `prefix(line)` is the expanded
+/// coordinate of `line`.
+fn expanded_offsets(len: usize, occupied: &HashSet<usize>, expansion: usize) -> PrefixSum<usize> {
+    (1..=len)
+        .map(|line| match occupied.contains(&line) {
+            true => 1,
+            false => expansion,
+        })
+        .collect()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    solve(input, 2)
+fn pairwise_distance_sum(coordinates: impl Iterator<Item = usize>) -> usize {
+    let mut coordinates: Vec<usize> = coordinates.collect();
+    coordinates.sort_unstable();
+    let sums: PrefixSum<usize> = coordinates.iter().copied().collect();
+
+    coordinates
+        .iter()
+        .enumerate()
+        .map(|(index, coordinate)| coordinate * index - sums.prefix(index))
+        .sum()
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    solve(input, 1000000)
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Image;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Image {
+        Image::new(input)
+    }
+
+    fn part_one(image: &Image) -> Option<usize> {
+        Some(image.distance_sum(2, 2))
+    }
+
+    fn part_two(image: &Image) -> Option<usize> {
+        Some(image.distance_sum(1000000, 1000000))
+    }
 }
 
 #[cfg(test)]
@@ -125,4 +129,13 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(82000210));
     }
+
+    #[test]
+    fn test_expansions() {
+        let image = Image::new(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(image.distance_sum(10, 10), 1030);
+        assert_eq!(image.distance_sum(100, 100), 8410);
+        assert_eq!(image.distance_sum(2, 10), 742);
+        assert_eq!(image.distance_sum(10, 2), 662);
+    }
 }
@@ -1,9 +1,8 @@
-use itertools::Itertools;
-use std::collections::HashMap;
+use advent_of_code::{memoize, Puzzle};
 
 advent_of_code::solution!(12);
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 enum Condition {
     Operational,
     Damaged,
@@ -23,7 +22,6 @@ impl From<char> for Condition {
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone)]
 struct Record {
     conditions: Vec<Condition>,
     damaged_account: Vec<usize>,
@@ -45,12 +43,6 @@ impl From<&str> for Record {
     }
 }
 
-impl From<String> for Record {
-    fn from(value: String) -> Self {
-        Self::from(value.as_str())
-    }
-}
-
 impl Record {
     fn new(conditions: Vec<Condition>, damaged_account: Vec<usize>) -> Self {
         Self {
@@ -59,95 +51,85 @@ impl Record {
         }
     }
 
-    fn possible_arrangements(&self, cache: &mut HashMap<Record, usize>) -> usize {
-        if let Some(&count) = cache.get(self) {
-            return count;
-        }
-
-        if self.damaged_account.is_empty() {
-            let count = match self.conditions.iter().any(|&c| c == Damaged) {
-                true => 0,
-                false => 1,
-            };
-
-            cache.insert(self.clone(), count);
-            return count;
-        }
-
-        let needed_space =
-            self.damaged_account.iter().sum::<usize>() + self.damaged_account.len() - 1;
-        if self.conditions.len() < needed_space {
-            cache.insert(self.clone(), 0);
-            return 0;
-        }
-
-        let first = self.conditions[0];
-        if first == Operational {
-            let count = Self::new(self.conditions[1..].to_vec(), self.damaged_account.clone())
-                .possible_arrangements(cache);
-            cache.insert(self.clone(), count);
-            return count;
-        }
-
-        let damaged = self.damaged_account[0];
-        let are_all_non_operational = self.conditions[..damaged].iter().all(|c| *c != Operational);
-        let end = (damaged + 1).min(self.conditions.len());
-
-        let mut count = 0;
-
-        if are_all_non_operational
-            && ((self.conditions.len() > damaged && self.conditions[damaged] != Damaged)
-                || self.conditions.len() <= damaged)
-        {
-            count += Self::new(
-                self.conditions[end..].to_vec(),
-                self.damaged_account[1..].to_vec(),
-            )
-            .possible_arrangements(cache);
-        }
-
-        if first == Unknown {
-            count += Self::new(self.conditions[1..].to_vec(), self.damaged_account.clone())
-                .possible_arrangements(cache);
-        }
-
-        cache.insert(self.clone(), count);
-
-        count
+    /// The record with its conditions repeated five times, separated by
+    /// unknown ones, and its damaged account repeated as often.
+    fn unfold(&self) -> Self {
+        Self::new(
+            vec![self.conditions.clone(); 5].join(&Unknown),
+            self.damaged_account.repeat(5),
+        )
     }
-}
-
-fn solve(records: Vec<Record>) -> Option<usize> {
-    let mut cache = HashMap::new();
 
-    Some(
-        records
-            .iter()
-            .map(|c| c.possible_arrangements(&mut cache))
-            .sum(),
-    )
-}
-
-pub fn part_one(input: &str) -> Option<usize> {
-    let records = input.lines().map(Record::from).collect();
-    solve(records)
+    fn possible_arrangements(&self) -> usize {
+        let conditions = &self.conditions;
+        let damaged_account = &self.damaged_account;
+
+        memoize((0, 0), |arrangements, (start, group)| {
+            let conditions = &conditions[start..];
+            let damaged_account = &damaged_account[group..];
+
+            if damaged_account.is_empty() {
+                return match conditions.contains(&Damaged) {
+                    true => 0,
+                    false => 1,
+                };
+            }
+
+            let needed_space = damaged_account.iter().sum::<usize>() + damaged_account.len() - 1;
+            if conditions.len() < needed_space {
+                return 0;
+            }
+
+            let first = conditions[0];
+            if first == Operational {
+                return arrangements((start + 1, group));
+            }
+
+            let damaged = damaged_account[0];
+            let are_all_non_operational = conditions[..damaged].iter().all(|c| *c != Operational);
+            let end = (damaged + 1).min(conditions.len());
+
+            let mut count = 0;
+
+            if are_all_non_operational
+                && ((conditions.len() > damaged && conditions[damaged] != Damaged)
+                    || conditions.len() <= damaged)
+            {
+                count += arrangements((start + end, group + 1));
+            }
+
+            if first == Unknown {
+                count += arrangements((start + 1, group));
+            }
+
+            count
+        })
+    }
 }
 
-fn unfold(input: &str) -> String {
-    let mut parts = input.split_whitespace();
+struct Solution;
 
-    let conditions = parts.next().unwrap();
-    let conditions = (0..5).map(|_| conditions).join("?");
+impl Puzzle for Solution {
+    type Parsed<'a> = Vec<Record>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    let damaged_account = parts.next().unwrap();
-    let damaged_account = (0..5).map(|_| damaged_account).join(",");
+    fn parse(input: &str) -> Vec<Record> {
+        input.lines().map(Record::from).collect()
+    }
 
-    format!("{} {}", conditions, damaged_account)
-}
+    fn part_one(records: &Vec<Record>) -> Option<usize> {
+        Some(records.iter().map(Record::possible_arrangements).sum())
+    }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let records = input.lines().map(unfold).map(Record::from).collect();
-    solve(records)
+    fn part_two(records: &Vec<Record>) -> Option<usize> {
+        Some(
+            records
+                .iter()
+                .map(|record| record.unfold().possible_arrangements())
+                .sum(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,8 @@
-use advent_of_code::Matrix;
+use advent_of_code::{
+    visualize::{Color, Frame, Recorder, Style},
+    Matrix, Orientation, Puzzle, Reflection,
+};
+use std::io;
 
 advent_of_code::solution!(13);
 
@@ -32,71 +36,66 @@ impl From<&str> for Patterns {
     }
 }
 
-fn hamming_distance<T>(a: &[T], b: &[T]) -> usize
-where
-    T: PartialEq,
-{
-    if a.len() != b.len() {
-        panic!("patterns must be of equal length.");
+fn score(reflection: &Reflection) -> u32 {
+    let before = reflection.before as u32;
+    match reflection.orientation {
+        Orientation::Horizontal => before * 100,
+        Orientation::Vertical => before,
     }
-    a.iter().zip(b.iter()).filter(|(a, b)| a != b).count()
 }
 
 impl Patterns {
-    fn len(&self) -> usize {
-        self.patterns.rows
+    fn render(&self, reflection: &Reflection) -> Frame {
+        let mut frame = Frame::new(&self.patterns, |_, pattern| match pattern {
+            Ash => '.',
+            Rocks => '#',
+        });
+        frame.highlight(reflection.smudge_cells(), Style::background(Color::RED));
+        frame
     }
 
-    fn get(&self, index: usize) -> Vec<Pattern> {
-        self.patterns.get_row(index).clone()
+    fn summarize(&self, smudges: usize, recorder: &mut Recorder) -> u32 {
+        self.patterns
+            .reflections_with_smudges(smudges)
+            .iter()
+            .inspect(|reflection| recorder.record(|| self.render(reflection)))
+            .map(score)
+            .sum()
     }
+}
 
-    fn distance(&self, index: usize) -> usize {
-        let mut distance = 0;
-        let max_matches = (self.len() - (index + 1)).min(index + 1);
-        (0..max_matches).for_each(|shift| {
-            distance += hamming_distance(&self.get(index - shift), &self.get(index + shift + 1));
-        });
-        distance
-    }
+fn solve(patterns: &[Patterns], smudges: usize, recorder: &mut Recorder) -> u32 {
+    patterns
+        .iter()
+        .map(|p| p.summarize(smudges, recorder))
+        .sum()
+}
 
-    fn get_reflection_position(&self, distance: usize) -> Option<u32> {
-        (0..self.len() - 1)
-            .find(|index| self.distance(*index) == distance)
-            .map(|x| x as u32 + 1)
-    }
+struct Solution;
 
-    fn transpose(&self) -> Self {
-        Self {
-            patterns: self.patterns.transpose(),
-        }
-    }
+impl Puzzle for Solution {
+    type Parsed<'a> = Vec<Patterns>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-    fn summarize(&self, distance: usize) -> u32 {
-        self.get_reflection_position(distance).unwrap_or_default() * 100
-            + self
-                .transpose()
-                .get_reflection_position(distance)
-                .unwrap_or_default()
+    fn parse(input: &str) -> Vec<Patterns> {
+        input.split("\n\n").map(Patterns::from).collect()
     }
-}
 
-fn solve(input: &str, distance: usize) -> Option<u32> {
-    Some(
-        input
-            .split("\n\n")
-            .map(Patterns::from)
-            .map(|p| p.summarize(distance))
-            .sum(),
-    )
-}
+    fn part_one(patterns: &Vec<Patterns>) -> Option<u32> {
+        Some(solve(patterns, 0, &mut Recorder::disabled()))
+    }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    solve(input, 0)
-}
+    fn part_two(patterns: &Vec<Patterns>) -> Option<u32> {
+        Some(solve(patterns, 1, &mut Recorder::disabled()))
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    solve(input, 1)
+    /// Records the reflections of part two, with their smudges highlighted.
+    fn export(patterns: &Vec<Patterns>) -> io::Result<()> {
+        let mut recorder = Recorder::from_env();
+        solve(patterns, 1, &mut recorder);
+        recorder.save("13")
+    }
 }
 
 #[cfg(test)]
@@ -114,4 +113,21 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(400));
     }
+
+    #[test]
+    fn test_smudges() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let smudges: Vec<_> = input
+            .split("\n\n")
+            .map(|pattern| Patterns::from(pattern).patterns.reflections_with_smudges(1))
+            .map(|reflections| reflections[0].smudges.clone())
+            .collect();
+        assert_eq!(
+            smudges,
+            vec![
+                vec![((1, 1).into(), (6, 1).into())],
+                vec![((1, 5).into(), (2, 5).into())],
+            ]
+        );
+    }
 }
@@ -1,8 +1,13 @@
-use advent_of_code::Matrix;
+use advent_of_code::{
+    visualize::{Color, Frame, Recorder, Style},
+    Direction, Matrix, Position as P, Puzzle,
+};
 use std::{
     collections::HashMap,
     fmt::{Display, Write},
+    io,
 };
+type Position = P<usize>;
 
 advent_of_code::solution!(14);
 
@@ -13,11 +18,7 @@ enum Tile {
     Empty,
 }
 
-#[derive(PartialEq, Eq, Clone, Hash)]
-struct Solver {
-    tiles: Matrix<Tile>,
-}
-
+use Direction::*;
 use Tile::*;
 
 impl From<char> for Tile {
@@ -31,139 +32,352 @@ impl From<char> for Tile {
     }
 }
 
-impl Display for Tile {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ch = match self {
+impl Tile {
+    fn symbol(&self) -> char {
+        match self {
             Rounded => 'O',
             Cube => '#',
             Empty => '.',
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char(self.symbol())
+    }
+}
+
+/// A run of cells between cube rocks (or the edges) along a row or column.
+#[derive(Clone)]
+struct Segment {
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Segment {
+    fn len(&self) -> usize {
+        self.end - self.start + 1
+    }
+}
+
+/// The segments of one orientation and the segment every non-cube cell is in.
+#[derive(Clone)]
+struct Segments {
+    vertical: bool,
+    segments: Vec<Segment>,
+    cells: Matrix<usize>,
+    /// For every segment, the segment of the other orientation each of its
+    /// cells lies in, from start to end.
+    crossings: Vec<Vec<usize>>,
+}
+
+impl Segments {
+    /// Cuts every column (or row, if not `vertical`) at its cube rocks.
+    fn new(tiles: &Matrix<Tile>, vertical: bool) -> Self {
+        let (lines, len) = match vertical {
+            true => (tiles.cols, tiles.rows),
+            false => (tiles.rows, tiles.cols),
+        };
+        let at = |line: usize, offset: usize| -> Position {
+            match vertical {
+                true => (offset, line).into(),
+                false => (line, offset).into(),
+            }
         };
-        f.write_char(ch)
+
+        let mut segments = vec![];
+        let mut cells = tiles.map(|_| usize::MAX);
+        (1..=lines).for_each(|line| {
+            let mut start = None;
+            (1..=len + 1).for_each(|offset| {
+                let is_cube = offset > len || tiles[at(line, offset)] == Cube;
+                match (start, is_cube) {
+                    (None, false) => start = Some(offset),
+                    (Some(segment_start), true) => {
+                        segments.push(Segment {
+                            line,
+                            start: segment_start,
+                            end: offset - 1,
+                        });
+                        start = None;
+                    }
+                    _ => {}
+                }
+                if !is_cube {
+                    cells[at(line, offset)] = segments.len();
+                }
+            });
+        });
+
+        Self {
+            vertical,
+            segments,
+            cells,
+            crossings: vec![],
+        }
     }
+
+    fn position(&self, segment: &Segment, offset: usize) -> Position {
+        match self.vertical {
+            true => Position::new(offset, segment.line),
+            false => Position::new(segment.line, offset),
+        }
+    }
+
+    fn cross(&mut self, other: &Segments) {
+        self.crossings = self
+            .segments
+            .iter()
+            .map(|segment| {
+                (segment.start..=segment.end)
+                    .map(|offset| other.cells[self.position(segment, offset)])
+                    .collect()
+            })
+            .collect();
+    }
+
+    /// The rocks packed into the segments towards `direction`, counted by
+    /// the segments of the other orientation they lie in.
+    fn recount(&self, direction: &Direction, counts: &[usize], other: &Segments) -> Vec<usize> {
+        let mut recounted = vec![0; other.segments.len()];
+        self.segments
+            .iter()
+            .zip(&self.crossings)
+            .zip(counts)
+            .for_each(|((segment, crossings), &count)| {
+                let occupied = match direction {
+                    Up | Left => &crossings[..count],
+                    Down | Right => &crossings[segment.len() - count..],
+                };
+                occupied
+                    .iter()
+                    .for_each(|&crossing| recounted[crossing] += 1);
+            });
+        recounted
+    }
+}
+
+/// Where the rounded rocks are: as read from the input, or as how many of
+/// them rest in every segment after the platform was tilted in `direction`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Rocks {
+    Loose(Vec<Position>),
+    Packed {
+        direction: Direction,
+        counts: Vec<usize>,
+    },
+}
+
+/// The tilts of one spin cycle, in order.
+const SPIN: [Direction; 4] = [Up, Left, Down, Right];
+
+#[derive(Clone)]
+struct Platform {
+    tiles: Matrix<Tile>,
+    columns: Segments,
+    rows: Segments,
+    rocks: Rocks,
+    load: usize,
 }
 
-impl From<&str> for Solver {
+impl From<&str> for Platform {
     fn from(value: &str) -> Self {
+        let tiles = Matrix::from(value);
+        let rocks: Vec<Position> = tiles
+            .indexes()
+            .into_iter()
+            .filter(|&position| tiles[position] == Rounded)
+            .collect();
+        let rows = tiles.rows;
+        let load = rocks.iter().map(|rock| rows + 1 - rock.row).sum();
+
+        let mut columns = Segments::new(&tiles, true);
+        let mut row_segments = Segments::new(&tiles, false);
+        columns.cross(&row_segments);
+        row_segments.cross(&columns);
+
         Self {
-            tiles: Matrix::from(value),
+            columns,
+            rows: row_segments,
+            tiles: tiles.map(|&tile| if tile == Rounded { Empty } else { tile }),
+            rocks: Rocks::Loose(rocks),
+            load,
         }
     }
 }
 
-impl Display for Solver {
+impl Display for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.tiles.row_iter() {
-            for tile in row {
-                write!(f, "{}", tile)?
-            }
-            writeln!(f)?
-        }
-        Ok(())
+        let mut tiles = self.tiles.clone();
+        self.rock_positions()
+            .into_iter()
+            .for_each(|position| tiles[position] = Rounded);
+        write!(f, "{}", tiles)
     }
 }
 
-impl Solver {
-    fn transpose(&self) -> Self {
-        Self {
-            tiles: self.tiles.transpose(),
+impl Platform {
+    fn segments(&self, direction: &Direction) -> &Segments {
+        match direction {
+            Up | Down => &self.columns,
+            Left | Right => &self.rows,
         }
     }
 
-    fn slide_to_head_in_row(row: &[Tile]) -> Vec<Tile> {
-        let mut slide_row = vec![Empty; row.len()];
-        let mut rounded_position = 0;
-        row.iter().enumerate().for_each(|(index, tile)| {
-            if *tile == Rounded {
-                slide_row[rounded_position] = Rounded;
-                rounded_position += 1;
-            }
-            if *tile == Cube {
-                slide_row[index] = Cube;
-                rounded_position = index + 1;
-            }
-        });
-        slide_row
+    fn rock_positions(&self) -> Vec<Position> {
+        let (direction, counts) = match &self.rocks {
+            Rocks::Loose(positions) => return positions.clone(),
+            Rocks::Packed { direction, counts } => (direction, counts),
+        };
+        let segments = self.segments(direction);
+        segments
+            .segments
+            .iter()
+            .zip(counts)
+            .flat_map(|(segment, &count)| {
+                let offsets = match direction {
+                    Up | Left => segment.start..segment.start + count,
+                    Down | Right => segment.end + 1 - count..segment.end + 1,
+                };
+                offsets.map(move |offset| segments.position(segment, offset))
+            })
+            .collect()
     }
 
-    fn slide_to_tail_in_row(row: &[Tile]) -> Vec<Tile> {
-        let mut reversed_row = row.to_owned();
-        reversed_row.reverse();
-        let mut reversed_slide_row = Self::slide_to_head_in_row(&reversed_row);
-        reversed_slide_row.reverse();
-        reversed_slide_row
+    /// Packs the rocks counted by the segments of `direction` and updates the load.
+    fn place(&mut self, direction: Direction, counts: Vec<usize>) {
+        let segments = self.segments(&direction);
+
+        let rows = self.tiles.rows;
+        let load = segments
+            .segments
+            .iter()
+            .zip(&counts)
+            .map(|(segment, &count)| match direction {
+                // rows `top..top + count`, weighted `rows + 1 - row`
+                Up | Down => {
+                    let top = match direction {
+                        Up => segment.start,
+                        _ => segment.end + 1 - count,
+                    };
+                    count * (rows + 1 - top) - count * count.saturating_sub(1) / 2
+                }
+                Left | Right => count * (rows + 1 - segment.line),
+            })
+            .sum();
+        debug_assert!(segments
+            .segments
+            .iter()
+            .zip(&counts)
+            .all(|(segment, &count)| count <= segment.len()));
+
+        self.load = load;
+        self.rocks = Rocks::Packed { direction, counts };
     }
 
-    fn slide_to_north(&self) -> Self {
-        self.transpose().slide_to_west().transpose()
+    /// Moves the rock counts straight from the segments they were packed in
+    /// to those of `direction`.
+    fn tilt(&mut self, direction: Direction) -> &mut Self {
+        let target = self.segments(&direction);
+        let counts = match &self.rocks {
+            Rocks::Loose(positions) => {
+                let mut counts = vec![0; target.segments.len()];
+                positions
+                    .iter()
+                    .for_each(|&position| counts[target.cells[position]] += 1);
+                counts
+            }
+            Rocks::Packed {
+                direction: from,
+                counts,
+            } => {
+                let source = self.segments(from);
+                match source.vertical == target.vertical {
+                    true => counts.clone(),
+                    false => source.recount(from, counts, target),
+                }
+            }
+        };
+        self.place(direction, counts);
+        self
     }
 
-    fn slide_to_west(&self) -> Self {
-        let tiles = self
-            .tiles
-            .row_iter()
-            .map(|tiles| Self::slide_to_head_in_row(tiles))
-            .collect::<Vec<Vec<Tile>>>()
-            .into();
-        Self { tiles }
+    fn cycle(&mut self) -> &mut Self {
+        SPIN.into_iter()
+            .fold(self, |platform, direction| platform.tilt(direction))
     }
 
-    fn slide_to_south(&self) -> Self {
-        self.transpose().slide_to_east().transpose()
+    /// The total load on the north support beams.
+    fn load(&self) -> usize {
+        self.load
     }
 
-    fn slide_to_east(&self) -> Self {
-        let tiles = self
-            .tiles
-            .row_iter()
-            .map(|tiles| Self::slide_to_tail_in_row(tiles))
-            .collect::<Vec<Vec<Tile>>>()
-            .into();
-        Self { tiles }
+    fn render(&self) -> Frame {
+        let mut frame = Frame::new(&self.tiles, |_, tile| tile.symbol());
+        let rocks = self.rock_positions();
+        rocks.iter().for_each(|&position| {
+            frame.set_symbol(position, Rounded.symbol());
+        });
+        frame.highlight(&rocks, Style::foreground(Color::YELLOW));
+        frame
     }
+}
+
+/// The number of spin cycles recorded by the export, as many as the example
+/// shows.
+const EXPORTED_CYCLES: usize = 3;
 
-    fn cycle(&self) -> Self {
-        self.slide_to_north()
-            .slide_to_west()
-            .slide_to_south()
-            .slide_to_east()
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Platform;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Platform {
+        Platform::from(input)
     }
 
-    fn load(&self) -> u32 {
-        self.tiles
-            .row_iter()
-            .enumerate()
-            .map(|(index, row)| {
-                row.iter().filter(|&tile| *tile == Rounded).count() as u32
-                    * (self.tiles.rows as u32 - index as u32)
-            })
-            .sum()
+    fn part_one(platform: &Platform) -> Option<usize> {
+        Some(platform.clone().tilt(Up).load())
     }
-}
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(Solver::from(input).slide_to_north().load())
-}
+    fn part_two(platform: &Platform) -> Option<usize> {
+        let mut platform = platform.clone();
+        let mut index_map = HashMap::new();
+        let mut index: usize = 0;
+        loop {
+            index_map.insert(platform.rocks.clone(), index);
+            platform.cycle();
+            index += 1;
+            if index_map.contains_key(&platform.rocks) {
+                break;
+            }
+        }
+        let cycle_start = index_map[&platform.rocks];
+        let cycle_length = index - cycle_start;
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut solver = Solver::from(input);
-    let mut index_map = HashMap::new();
-    let mut index: u32 = 0;
-    loop {
-        index_map.insert(solver.clone(), index);
-        solver = solver.cycle();
-        index += 1;
-        if index_map.contains_key(&solver) {
-            break;
+        for _ in 0..(1_000_000_000 - cycle_start) % cycle_length {
+            platform.cycle();
         }
-    }
-    let cycle_start = *index_map.get(&solver).unwrap();
-    let cycle_length = index - cycle_start;
 
-    for _ in 0..(1_000_000_000 - cycle_start) % cycle_length {
-        solver = solver.cycle();
+        Some(platform.load())
     }
 
-    Some(solver.load())
+    fn export(platform: &Platform) -> io::Result<()> {
+        let mut recorder = Recorder::from_env();
+        let mut platform = platform.clone();
+        recorder.record(|| platform.render());
+        for _ in 0..EXPORTED_CYCLES {
+            for direction in SPIN {
+                platform.tilt(direction);
+                recorder.record(|| platform.render());
+            }
+        }
+        recorder.save("14")
+    }
 }
 
 #[cfg(test)]
@@ -181,4 +395,27 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(64));
     }
+
+    #[test]
+    fn test_cycle() {
+        let mut platform = Platform::from(&*advent_of_code::template::read_file("examples", DAY));
+        platform.cycle();
+        assert_eq!(
+            platform.to_string(),
+            [
+                ".....#....",
+                "....#...O#",
+                "...OO##...",
+                ".OO#......",
+                ".....OOO#.",
+                ".O#...O#.#",
+                "....O#....",
+                "......OOOO",
+                "#...O###..",
+                "#..OO#....",
+                "",
+            ]
+            .join("\n")
+        );
+    }
 }
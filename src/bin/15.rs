@@ -1,45 +1,30 @@
-#![feature(ascii_char)]
-
-use itertools::Itertools;
-use std::hash::{Hash, Hasher};
+use advent_of_code::{ascii_codes, visualize::export_text, BucketMap, Puzzle};
+use std::{
+    fmt::{Display, Write},
+    hash::{BuildHasher, BuildHasherDefault, Hash, Hasher},
+    io,
+};
 
 advent_of_code::solution!(15);
 
-struct StringHasher(usize);
-
-#[derive(Clone, PartialEq, Eq)]
-struct CustomizeString(String);
-
-#[derive(Clone)]
-struct Lens {
-    label: CustomizeString,
-    focal_length: usize,
-}
+const BOXES: usize = 256;
 
-#[derive(Clone)]
-struct Box {
-    lens_slots: Vec<Lens>,
-}
+/// The HASH algorithm, fed one ASCII code at a time.
+#[derive(Default)]
+struct StringHasher(usize);
 
-struct LensMapIterItem<'a> {
-    lens: &'a Lens,
-    box_index: usize,
-    slot_index: usize,
-}
+type HashBuilder = BuildHasherDefault<StringHasher>;
 
-struct LensMapIter<'a> {
-    boxes: &'a Vec<Box>,
-    box_index: usize,
-    slot_index: usize,
-}
+/// A label hashing only its ASCII codes, unlike `str`, which also writes a
+/// terminator.
+#[derive(Clone, PartialEq, Eq)]
+struct Label(String);
 
-struct LensMap {
-    boxes: Vec<Box>,
-}
+type LensMap = BucketMap<Label, usize, HashBuilder>;
 
 enum Operation {
-    Insert(Lens),
-    Remove(CustomizeString),
+    Insert(Label, usize),
+    Remove(Label),
 }
 
 use Operation::*;
@@ -56,175 +41,99 @@ impl Hasher for StringHasher {
     }
 }
 
-impl StringHasher {
-    fn new() -> Self {
-        StringHasher(0)
-    }
-}
-
-impl Hash for CustomizeString {
+impl Hash for Label {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.0
-            .chars()
-            .for_each(|ch| state.write(&[ch.as_ascii().unwrap().to_u8()]))
+        ascii_codes(&self.0).for_each(|code| state.write(&[code]))
     }
 }
 
-impl From<&str> for CustomizeString {
+impl From<&str> for Label {
     fn from(value: &str) -> Self {
         Self(value.to_string())
     }
 }
 
-impl CustomizeString {
+impl Display for Label {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Label {
     fn calc_hash(&self) -> usize {
-        let mut hasher = StringHasher::new();
-        self.hash(&mut hasher);
-        hasher.finish() as usize
+        HashBuilder::default().hash_one(self) as usize
     }
 }
 
 impl From<&str> for Operation {
     fn from(value: &str) -> Self {
-        if value.contains('=') {
-            let mut parts = value.split('=');
-            let label = CustomizeString::from(parts.next().unwrap());
-            let focal_length = parts.next().unwrap().parse().unwrap();
-            let lens = Lens {
-                label,
-                focal_length,
-            };
-            Insert(lens)
-        } else if value.contains('-') {
-            let mut parts = value.split('-');
-            let label = CustomizeString::from(parts.next().unwrap());
-            Remove(label)
+        if let Some((label, focal_length)) = value.split_once('=') {
+            Insert(label.into(), focal_length.parse().unwrap())
+        } else if let Some(label) = value.strip_suffix('-') {
+            Remove(label.into())
         } else {
             panic!("Could not resolve operation.");
         }
     }
 }
 
-impl Lens {
-    fn calc_hash(&self) -> usize {
-        self.label.calc_hash()
-    }
-}
-
-impl Box {
-    fn new() -> Self {
-        Self { lens_slots: vec![] }
+impl Operation {
+    fn apply(self, map: &mut LensMap) {
+        match self {
+            Insert(label, focal_length) => {
+                map.insert(label, focal_length);
+            }
+            Remove(label) => {
+                map.remove(&label);
+            }
+        }
     }
 }
 
-impl<'a> LensMapIter<'a> {
-    fn new(map: &'a LensMap) -> Self {
-        Self {
-            boxes: &map.boxes,
-            box_index: 0,
-            slot_index: 0,
-        }
-    }
+/// The boxes after every step of the initialization sequence.
+fn trace(steps: &[&str]) -> String {
+    let mut map = LensMap::with_hasher(BOXES, HashBuilder::default());
+    let mut trace = String::new();
+    steps.iter().for_each(|step| {
+        Operation::from(*step).apply(&mut map);
+        writeln!(trace, "After \"{}\":\n{}", step, map).unwrap();
+    });
+    trace
 }
 
-impl<'a> Iterator for LensMapIter<'a> {
-    type Item = LensMapIterItem<'a>;
+struct Solution;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.box_index > 255 {
-                return None;
-            }
-            let lens_slots = &self.boxes[self.box_index].lens_slots;
-            if self.slot_index >= lens_slots.len() {
-                self.box_index += 1;
-                self.slot_index = 0;
-                continue;
-            }
-            let lens = &lens_slots[self.slot_index];
-            let item = Self::Item {
-                lens,
-                box_index: self.box_index,
-                slot_index: self.slot_index,
-            };
-            self.slot_index += 1;
-            return Some(item);
-        }
-    }
-}
+impl Puzzle for Solution {
+    type Parsed<'a> = Vec<&'a str>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-impl LensMap {
-    fn new() -> Self {
-        Self {
-            boxes: vec![Box::new(); 256],
-        }
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        input.lines().next().unwrap().split(',').collect()
     }
 
-    fn insert(&mut self, lens: Lens) {
-        let box_index = lens.calc_hash();
-        let lens_slots = &mut self.boxes[box_index].lens_slots;
-        let slot_position = lens_slots
-            .iter()
-            .find_position(|inner_lens| inner_lens.label == lens.label);
-        if let Some((slot_index, _)) = slot_position {
-            lens_slots[slot_index] = lens;
-        } else {
-            lens_slots.push(lens);
-        }
+    fn part_one(steps: &Self::Parsed<'_>) -> Option<usize> {
+        Some(
+            steps
+                .iter()
+                .map(|&step| Label::from(step).calc_hash())
+                .sum(),
+        )
     }
 
-    fn remove(&mut self, label: CustomizeString) {
-        let box_index = label.calc_hash();
-        let lens_slots = &mut self.boxes[box_index].lens_slots;
-        let slot_position = lens_slots
+    fn part_two(steps: &Self::Parsed<'_>) -> Option<usize> {
+        let mut map = LensMap::with_hasher(BOXES, HashBuilder::default());
+        steps
             .iter()
-            .find_position(|inner_lens| inner_lens.label == label);
-        if let Some((slot_index, _)) = slot_position {
-            lens_slots.remove(slot_index);
-        }
-    }
-
-    fn apply_operation(&mut self, operation: Operation) {
-        match operation {
-            Insert(lens) => self.insert(lens),
-            Remove(label) => self.remove(label),
-        }
+            .for_each(|&step| Operation::from(step).apply(&mut map));
+        Some(map.weighted_sum(|_, &focal_length| focal_length))
     }
 
-    fn iter(&self) -> LensMapIter {
-        LensMapIter::new(self)
+    fn export(steps: &Self::Parsed<'_>) -> io::Result<()> {
+        export_text("15.txt", &trace(steps))
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(
-        input
-            .lines()
-            .next()
-            .unwrap()
-            .split(',')
-            .map(CustomizeString::from)
-            .map(|s| s.calc_hash())
-            .sum(),
-    )
-}
-
-pub fn part_two(input: &str) -> Option<usize> {
-    let mut map = LensMap::new();
-    input
-        .lines()
-        .next()
-        .unwrap()
-        .split(',')
-        .map(Operation::from)
-        .for_each(|op| map.apply_operation(op));
-    Some(
-        map.iter()
-            .map(|item| (item.box_index + 1) * (item.slot_index + 1) * item.lens.focal_length)
-            .sum(),
-    )
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,4 +149,17 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(145));
     }
+
+    #[test]
+    fn test_trace() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let trace = trace(&Solution::parse(&input));
+        let steps: Vec<&str> = trace.split("\n\n").collect();
+        assert_eq!(steps[0], "After \"rn=1\":\nBox 0: [rn 1]");
+        assert_eq!(steps[1], "After \"cm-\":\nBox 0: [rn 1]");
+        assert_eq!(
+            steps[10],
+            "After \"ot=7\":\nBox 0: [rn 1] [cm 2]\nBox 3: [ot 7] [ab 5] [pc 6]"
+        );
+    }
 }
@@ -1,5 +1,8 @@
-use advent_of_code::{Direction, Matrix, Movable, Position as P};
-use std::{collections::HashSet, vec};
+use advent_of_code::{
+    visualize::{Color, Frame, Recorder, Style},
+    Direction, Matrix, Movable, Position as P, Puzzle,
+};
+use std::{collections::HashSet, io, vec};
 type Position = P<usize>;
 
 advent_of_code::solution!(16);
@@ -61,6 +64,18 @@ impl From<char> for Tile {
     }
 }
 
+impl Tile {
+    fn symbol(&self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::Mirror(Mirror::Slash) => '/',
+            Tile::Mirror(Mirror::BackSlash) => '\\',
+            Tile::Splitter(Splitter::Horizontal) => '-',
+            Tile::Splitter(Splitter::Vertical) => '|',
+        }
+    }
+}
+
 use Direction::*;
 
 impl Default for Beam {
@@ -159,14 +174,17 @@ impl Contraption {
         Some(beams)
     }
 
-    fn energizes(&self, beam: &Beam) -> u32 {
+    fn energizes(&self, beam: &Beam, recorder: &mut Recorder) -> u32 {
         let mut beams = vec![*beam];
         let mut visited = HashSet::new();
 
-        while let Some(beam) = beams.pop() {
-            if let Some(mut moved_beams) = self.move_beam(&beam, &mut visited) {
-                beams.append(&mut moved_beams);
-            }
+        while !beams.is_empty() {
+            beams = beams
+                .iter()
+                .filter_map(|beam| self.move_beam(beam, &mut visited))
+                .flatten()
+                .collect();
+            recorder.record(|| self.render(&visited));
         }
 
         let mut visited_positions = HashSet::new();
@@ -177,6 +195,15 @@ impl Contraption {
         visited_positions.len() as u32
     }
 
+    fn render(&self, visited: &HashSet<Beam>) -> Frame {
+        let mut frame = Frame::new(&self.tiles, |_, tile| tile.symbol());
+        frame.highlight(
+            visited.iter().map(Beam::position),
+            Style::background(Color::YELLOW),
+        );
+        frame
+    }
+
     fn get_all_edge_beams(&self) -> Vec<Beam> {
         let mut beams = vec![];
 
@@ -200,17 +227,34 @@ impl Contraption {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(Contraption::from(input).energizes(&Beam::default()))
-}
+struct Solution;
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let contraption = Contraption::from(input);
-    contraption
-        .get_all_edge_beams()
-        .iter()
-        .map(|beam| contraption.energizes(beam))
-        .max()
+impl Puzzle for Solution {
+    type Parsed<'a> = Contraption;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Contraption {
+        Contraption::from(input)
+    }
+
+    fn part_one(contraption: &Contraption) -> Option<u32> {
+        Some(contraption.energizes(&Beam::default(), &mut Recorder::disabled()))
+    }
+
+    fn part_two(contraption: &Contraption) -> Option<u32> {
+        contraption
+            .get_all_edge_beams()
+            .iter()
+            .map(|beam| contraption.energizes(beam, &mut Recorder::disabled()))
+            .max()
+    }
+
+    fn export(contraption: &Contraption) -> io::Result<()> {
+        let mut recorder = Recorder::from_env();
+        contraption.energizes(&Beam::default(), &mut recorder);
+        recorder.save("16")
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,6 @@
-use advent_of_code::{dijkstra::Bound, dijkstra_search, Direction, Matrix, Movable, Position as P};
+use advent_of_code::{
+    dijkstra::Bound, dijkstra_search, Direction, Matrix, Movable, Position as P, Puzzle,
+};
 use std::ops::Add;
 use Direction::*;
 type Position = P<usize>;
@@ -201,20 +203,31 @@ impl Solver {
     }
 }
 
-fn solve(input: &str, is_ultra: bool) -> Option<u32> {
-    let solver = Solver::from(input);
+fn solve(solver: &Solver, is_ultra: bool) -> Option<u32> {
     let blocks = &solver.blocks;
     let start = (1, 1).into();
     let goal = (blocks.rows, blocks.cols).into();
     solver.minimize_heat_loss(&start, &goal, is_ultra)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    solve(input, false)
-}
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Solver;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Solver {
+        Solver::from(input)
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    solve(input, true)
+    fn part_one(solver: &Solver) -> Option<u32> {
+        solve(solver, false)
+    }
+
+    fn part_two(solver: &Solver) -> Option<u32> {
+        solve(solver, true)
+    }
 }
 
 #[cfg(test)]
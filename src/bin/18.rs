@@ -1,8 +1,6 @@
-use advent_of_code::{number_of_interiors, Direction, Movable, Position as P};
+use advent_of_code::{Direction, PolygonBuilder, Puzzle};
 use Direction::*;
 
-type Position = P<isize>;
-
 advent_of_code::solution!(18);
 
 struct Command {
@@ -10,8 +8,6 @@ struct Command {
     steps: usize,
 }
 
-struct Digger(Movable<isize>);
-
 struct BigPlan;
 
 impl Command {
@@ -57,29 +53,6 @@ impl Command {
     }
 }
 
-impl Digger {
-    fn new() -> Self {
-        Self(Movable::new((1, 1).into(), Right))
-    }
-
-    fn move_to(&mut self, direction: &Direction, steps: usize) -> Vec<Position> {
-        (0..steps)
-            .map(|_| self.0.turn_to(direction).move_forward(1).position)
-            .collect()
-    }
-
-    fn move_with_command(&mut self, command: &Command) -> Vec<Position> {
-        self.move_to(&command.direction, command.steps)
-    }
-
-    fn start_move(&mut self, commands: &[Command]) -> Vec<Position> {
-        commands
-            .iter()
-            .flat_map(|command| self.move_with_command(command))
-            .collect()
-    }
-}
-
 impl BigPlan {
     fn get_big_plan(value: &str) -> Vec<Command> {
         value.lines().map(Command::parse_command).collect()
@@ -90,19 +63,35 @@ impl BigPlan {
     }
 }
 
-fn solve(circle: &[Position]) -> Option<usize> {
-    let interiors = number_of_interiors(circle);
-    Some(interiors + circle.len())
+/// The lagoon dug along the plan, trench included, starting at `(1, 1)`.
+fn solve(plan: &[Command]) -> Option<usize> {
+    let mut lagoon = PolygonBuilder::new((1, 1).into());
+    lagoon.extend(
+        plan.iter()
+            .map(|command| (command.direction, command.steps)),
+    );
+    Some(lagoon.area_with_boundary())
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let circle = Digger::new().start_move(&BigPlan::get_big_plan(input));
-    solve(&circle)
-}
+struct Solution;
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let circle = Digger::new().start_move(&BigPlan::get_crazy_plan(input));
-    solve(&circle)
+impl Puzzle for Solution {
+    /// The plan as read, and as decoded from the colors.
+    type Parsed<'a> = (Vec<Command>, Vec<Command>);
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        (BigPlan::get_big_plan(input), BigPlan::get_crazy_plan(input))
+    }
+
+    fn part_one((big_plan, _): &Self::Parsed<'_>) -> Option<usize> {
+        solve(big_plan)
+    }
+
+    fn part_two((_, crazy_plan): &Self::Parsed<'_>) -> Option<usize> {
+        solve(crazy_plan)
+    }
 }
 
 #[cfg(test)]
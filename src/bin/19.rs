@@ -1,34 +1,49 @@
-use std::{collections::HashMap, ops::Range};
+use advent_of_code::Puzzle;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
 
 advent_of_code::solution!(19);
 
-struct Part {
-    looking: usize,
-    musical: usize,
-    aerodynamic: usize,
-    shiny: usize,
+/// Category names, in the order ratings are stored in.
+#[derive(Default)]
+struct Categories(Vec<String>);
+
+impl Categories {
+    fn index(&mut self, name: &str) -> usize {
+        match self.0.iter().position(|category| category == name) {
+            Some(index) => index,
+            None => {
+                self.0.push(name.to_string());
+                self.0.len() - 1
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
 }
 
-#[derive(Copy, Clone)]
-enum Category {
-    Looking,
-    Musical,
-    Aerodynamic,
-    Shiny,
+struct Part {
+    ratings: Vec<usize>,
 }
 
+#[derive(Clone, Copy)]
 enum Operator {
     Less,
     Greater,
 }
 
+#[derive(Clone, Copy)]
 struct Condition {
-    category: Category,
+    category: usize,
     operator: Operator,
     rate: usize,
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum Finished {
     Accepted,
     Rejected,
@@ -51,56 +66,35 @@ struct Workflow {
 }
 
 struct Solver {
+    categories: Categories,
     workflows: HashMap<String, Workflow>,
     parts: Vec<Part>,
 }
 
-impl From<&str> for Part {
-    fn from(value: &str) -> Self {
-        let rate_info = &value.replace(['{', '}'], "");
-        let mut rates = rate_info.split(',');
-        let looking = Part::extract_rate(rates.next().unwrap());
-        let musical = Part::extract_rate(rates.next().unwrap());
-        let aerodynamic = Part::extract_rate(rates.next().unwrap());
-        let shiny = Part::extract_rate(rates.next().unwrap());
-        Self {
-            looking,
-            musical,
-            aerodynamic,
-            shiny,
-        }
-    }
-}
-
 impl Part {
-    fn extract_rate(value: &str) -> usize {
-        value.split('=').last().unwrap().parse().unwrap()
-    }
-
-    fn rating(&self) -> usize {
-        self.looking + self.musical + self.aerodynamic + self.shiny
+    fn new(value: &str, categories: &mut Categories) -> Self {
+        let mut ratings = vec![];
+        value
+            .trim_matches(['{', '}'])
+            .split(',')
+            .for_each(|rating| {
+                let (name, rate) = rating.split_once('=').unwrap();
+                let category = categories.index(name);
+                if ratings.len() <= category {
+                    ratings.resize(category + 1, 0);
+                }
+                ratings[category] = rate.parse().unwrap();
+            });
+        Self { ratings }
     }
 
-    fn get_rate(&self, category: &Category) -> usize {
-        use Category::*;
-        match category {
-            Looking => self.looking,
-            Musical => self.musical,
-            Aerodynamic => self.aerodynamic,
-            Shiny => self.shiny,
-        }
+    /// Categories the part has no rating for are rated zero.
+    fn get_rate(&self, category: usize) -> usize {
+        self.ratings.get(category).copied().unwrap_or_default()
     }
-}
 
-impl From<char> for Category {
-    fn from(value: char) -> Self {
-        match value {
-            'x' => Self::Looking,
-            'm' => Self::Musical,
-            'a' => Self::Aerodynamic,
-            's' => Self::Shiny,
-            _ => panic!("Invalid category"),
-        }
+    fn rating(&self) -> usize {
+        self.ratings.iter().sum()
     }
 }
 
@@ -114,24 +108,23 @@ impl From<char> for Operator {
     }
 }
 
-impl From<&str> for Condition {
-    fn from(value: &str) -> Self {
-        let mut chars = value.chars();
-        let category = chars.next().unwrap().into();
+impl Condition {
+    fn new(value: &str, categories: &mut Categories) -> Self {
+        let split = value.find(['<', '>']).expect("Invalid condition");
+        let (name, rest) = value.split_at(split);
+        let mut chars = rest.chars();
         let operator = chars.next().unwrap().into();
-        let rate = String::from_iter(chars).parse().unwrap();
+        let rate = chars.as_str().parse().unwrap();
         Self {
-            category,
+            category: categories.index(name),
             operator,
             rate,
         }
     }
-}
 
-impl Condition {
     fn resolve(&self, part: &Part) -> bool {
         use Operator::*;
-        let value = part.get_rate(&self.category);
+        let value = part.get_rate(self.category);
         match self.operator {
             Less => value < self.rate,
             Greater => value > self.rate,
@@ -139,16 +132,6 @@ impl Condition {
     }
 }
 
-impl From<&str> for Finished {
-    fn from(value: &str) -> Self {
-        match value {
-            "A" => Self::Accepted,
-            "R" => Self::Rejected,
-            _ => panic!("Invalid finished state"),
-        }
-    }
-}
-
 impl From<&str> for Destination {
     fn from(value: &str) -> Self {
         use Finished::*;
@@ -160,236 +143,331 @@ impl From<&str> for Destination {
     }
 }
 
-impl From<&str> for Rule {
-    fn from(value: &str) -> Self {
-        if value.contains(':') {
-            let mut parts = value.split(':');
-            let condition = Some(parts.next().unwrap().into());
-            let destination = parts.next().unwrap().into();
-            return Self {
-                condition,
-                destination,
-            };
-        }
-        Self {
-            condition: None,
-            destination: value.into(),
+impl Rule {
+    fn new(value: &str, categories: &mut Categories) -> Self {
+        match value.split_once(':') {
+            Some((condition, destination)) => Self {
+                condition: Some(Condition::new(condition, categories)),
+                destination: destination.into(),
+            },
+            None => Self {
+                condition: None,
+                destination: value.into(),
+            },
         }
     }
 }
 
-impl Rule {
-    fn resolve(&self, part: &Part) -> Option<&Destination> {
-        match &self.condition {
-            Some(condition) => match condition.resolve(part) {
-                true => Some(&self.destination),
-                false => None,
-            },
-            None => Some(&self.destination),
+impl Workflow {
+    fn new(value: &str, categories: &mut Categories) -> Self {
+        let (name, rules) = value.trim_end_matches('}').split_once('{').unwrap();
+        let rules = rules
+            .split(',')
+            .map(|rule| Rule::new(rule, categories))
+            .collect();
+        Self {
+            name: name.to_string(),
+            rules,
         }
     }
 }
 
-impl From<&str> for Workflow {
-    fn from(value: &str) -> Self {
-        let mut value = value.to_string();
-        value.pop();
-        let mut parts = value.split('{');
-        let name = parts.next().unwrap().to_string();
-        let rules = parts.next().unwrap().split(',').map(Rule::from).collect();
-        Self { name, rules }
+/// A hyper-rectangle of parts, holding the ratings allowed in every category.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Block(Vec<Range<usize>>);
+
+impl Block {
+    fn new(categories: usize, bounds: Range<usize>) -> Self {
+        Self(vec![bounds; categories])
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().any(|range| range.is_empty())
+    }
+
+    fn distinct_combinations(&self) -> usize {
+        self.0.iter().map(|range| range.len()).product()
+    }
+
+    /// The parts of the block that pass and fail `condition`.
+    fn split(&self, condition: &Condition) -> (Self, Self) {
+        use Operator::*;
+        let Range { start, end } = self.0[condition.category];
+        let threshold = match condition.operator {
+            Less => condition.rate,
+            Greater => condition.rate + 1,
+        };
+        let below = start..end.min(threshold);
+        let above = start.max(threshold)..end;
+        let (pass, fail) = match condition.operator {
+            Less => (below, above),
+            Greater => (above, below),
+        };
+
+        let with = |range: Range<usize>| {
+            let mut block = self.clone();
+            block.0[condition.category] = range;
+            block
+        };
+        (with(pass), with(fail))
     }
 }
 
-impl Workflow {
-    fn resolve(&self, part: &Part) -> &Destination {
-        for rule in &self.rules {
-            match rule.resolve(part) {
-                None => continue,
-                Some(destination) => return destination,
+/// The workflows compiled into a single tree of conditions.
+enum Node {
+    Leaf(Finished),
+    Branch {
+        condition: Condition,
+        pass: Box<Node>,
+        fail: Box<Node>,
+    },
+}
+
+impl Node {
+    fn classify(&self, part: &Part) -> Finished {
+        match self {
+            Node::Leaf(finished) => *finished,
+            Node::Branch {
+                condition,
+                pass,
+                fail,
+            } => match condition.resolve(part) {
+                true => pass.classify(part),
+                false => fail.classify(part),
+            },
+        }
+    }
+
+    /// Disjoint blocks covering every accepted part of `block`.
+    fn accepted(&self, block: Block) -> Vec<Block> {
+        if block.is_empty() {
+            return vec![];
+        }
+        match self {
+            Node::Leaf(Finished::Accepted) => vec![block],
+            Node::Leaf(Finished::Rejected) => vec![],
+            Node::Branch {
+                condition,
+                pass,
+                fail,
+            } => {
+                let (pass_block, fail_block) = block.split(condition);
+                [pass.accepted(pass_block), fail.accepted(fail_block)].concat()
             }
         }
-        panic!("No destination found");
+    }
+
+    fn count_accepted(&self, block: Block) -> usize {
+        self.accepted(block)
+            .iter()
+            .map(Block::distinct_combinations)
+            .sum()
     }
 }
 
 impl From<&str> for Solver {
     fn from(value: &str) -> Self {
-        let mut parts = value.split("\n\n");
-        let workflows = HashMap::from_iter(
-            parts
-                .next()
-                .unwrap()
-                .lines()
-                .map(Workflow::from)
-                .map(|workflow| (workflow.name.clone(), workflow)),
-        );
-        let parts = parts.next().unwrap().lines().map(Part::from).collect();
-        Self { workflows, parts }
+        let mut categories = Categories::default();
+        let (workflows, parts) = value.split_once("\n\n").unwrap();
+        let workflows = workflows
+            .lines()
+            .map(|workflow| Workflow::new(workflow, &mut categories))
+            .map(|workflow| (workflow.name.clone(), workflow))
+            .collect();
+        let parts = parts
+            .lines()
+            .map(|part| Part::new(part, &mut categories))
+            .collect();
+        Self {
+            categories,
+            workflows,
+            parts,
+        }
     }
 }
 
+const FIRST_WORKFLOW: &str = "in";
+
 impl Solver {
-    fn first_workflow(&self) -> &Workflow {
-        &self.workflows["in"]
+    fn block(&self, bounds: Range<usize>) -> Block {
+        Block::new(self.categories.len(), bounds)
     }
 
-    fn resolve(&self, part: &Part) -> &Finished {
-        let mut workflow = self.first_workflow();
+    /// Compiles the workflows starting at `in`, leaving out the rules no part
+    /// can ever be sent on by, or explains why they cannot be.
+    fn compile(&self) -> Result<Node, String> {
+        if let Some(cycle) = self.find_cycle() {
+            return Err(format!("Workflows loop: {}", cycle.join(" -> ")));
+        }
+        let unreachable = self
+            .unreachable_rules(self.block(0..usize::MAX))
+            .into_iter()
+            .collect();
+        Ok(self.compile_rules(FIRST_WORKFLOW, 0, &unreachable))
+    }
+
+    fn compile_rules(
+        &self,
+        name: &str,
+        index: usize,
+        unreachable: &HashSet<(String, usize)>,
+    ) -> Node {
         use Destination::*;
-        loop {
-            match workflow.resolve(part) {
-                Workflow(name) => workflow = &self.workflows[name],
-                End(result) => return result,
+        let rules = &self.workflows[name].rules;
+        let Some(rule) = rules.get(index) else {
+            // only parts that were sent on earlier get here
+            return Node::Leaf(Finished::Rejected);
+        };
+        if unreachable.contains(&(name.to_string(), index)) {
+            return self.compile_rules(name, index + 1, unreachable);
+        }
+
+        let destination = match &rule.destination {
+            End(finished) => Node::Leaf(*finished),
+            Workflow(next) => self.compile_rules(next, 0, unreachable),
+        };
+        match rule.condition {
+            Some(condition) => Node::Branch {
+                condition,
+                pass: Box::new(destination),
+                fail: Box::new(self.compile_rules(name, index + 1, unreachable)),
+            },
+            None => destination,
+        }
+    }
+
+    /// A chain of workflows reachable from `in` that leads back to its first
+    /// one, if there is any.
+    fn find_cycle(&self) -> Option<Vec<String>> {
+        fn visit<'a>(
+            solver: &'a Solver,
+            name: &'a str,
+            path: &mut Vec<&'a str>,
+            done: &mut HashSet<&'a str>,
+        ) -> Option<Vec<String>> {
+            if let Some(start) = path.iter().position(|&visiting| visiting == name) {
+                return Some(path[start..].iter().map(|name| name.to_string()).collect());
+            }
+            if !done.insert(name) {
+                return None;
             }
+            path.push(name);
+            let cycle =
+                solver.workflows[name]
+                    .rules
+                    .iter()
+                    .find_map(|rule| match &rule.destination {
+                        Destination::Workflow(next) => visit(solver, next, path, done),
+                        Destination::End(_) => None,
+                    });
+            path.pop();
+            cycle
         }
+
+        visit(self, FIRST_WORKFLOW, &mut vec![], &mut HashSet::new())
     }
 
-    fn rating_sum(&self) -> usize {
-        use Finished::*;
+    /// The rules, as workflow names and rule indexes, that no part within
+    /// `block` is ever sent on by.
+    fn unreachable_rules(&self, block: Block) -> Vec<(String, usize)> {
+        fn visit<'a>(
+            solver: &'a Solver,
+            name: &'a str,
+            mut block: Block,
+            path: &mut Vec<&'a str>,
+            taken: &mut HashSet<(&'a str, usize)>,
+        ) {
+            if path.contains(&name) {
+                return;
+            }
+            path.push(name);
+            for (index, rule) in solver.workflows[name].rules.iter().enumerate() {
+                let pass = match &rule.condition {
+                    Some(condition) => {
+                        let (pass, fail) = block.split(condition);
+                        block = fail;
+                        pass
+                    }
+                    None => block.clone(),
+                };
+                if !pass.is_empty() {
+                    taken.insert((name, index));
+                    if let Destination::Workflow(next) = &rule.destination {
+                        visit(solver, next, pass, path, taken);
+                    }
+                }
+                if rule.condition.is_none() {
+                    break;
+                }
+            }
+            path.pop();
+        }
+
+        let mut taken = HashSet::new();
+        visit(self, FIRST_WORKFLOW, block, &mut vec![], &mut taken);
+
+        let mut unreachable: Vec<(String, usize)> = self
+            .workflows
+            .values()
+            .flat_map(|workflow| {
+                (0..workflow.rules.len()).map(|index| (workflow.name.as_str(), index))
+            })
+            .filter(|rule| !taken.contains(rule))
+            .map(|(name, index)| (name.to_string(), index))
+            .collect();
+        unreachable.sort();
+        unreachable
+    }
+
+    fn rating_sum(&self, tree: &Node) -> usize {
         self.parts
             .iter()
-            .filter(|part| self.resolve(part) == &Accepted)
+            .filter(|part| tree.classify(part) == Finished::Accepted)
             .map(|part| part.rating())
             .sum()
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(Solver::from(input).rating_sum())
+/// The input with its workflows compiled once for both parts.
+struct Compiled {
+    solver: Solver,
+    tree: Result<Node, String>,
 }
 
-#[derive(Clone)]
-struct Segment {
-    looking: Range<usize>,
-    musical: Range<usize>,
-    aerodynamic: Range<usize>,
-    shiny: Range<usize>,
-}
-
-impl Default for Segment {
-    fn default() -> Self {
-        Self {
-            looking: 1..4001,
-            musical: 1..4001,
-            aerodynamic: 1..4001,
-            shiny: 1..4001,
-        }
+impl Compiled {
+    fn tree(&self) -> Option<&Node> {
+        self.tree.as_ref().ok()
     }
 }
 
-impl Segment {
-    fn distinct_combinations(&self) -> usize {
-        self.looking.len() * self.musical.len() * self.aerodynamic.len() * self.shiny.len()
-    }
+struct Solution;
 
-    fn update_segment(mut self, condition: &Condition) -> Self {
-        use Category::*;
-        use Operator::*;
-        let Condition {
-            category,
-            operator,
-            rate,
-        } = condition;
-        let Segment {
-            looking,
-            musical,
-            aerodynamic,
-            shiny,
-        } = &mut self;
-        let rate = *rate;
-        match (category, operator) {
-            (Looking, Less) => looking.end = rate,
-            (Looking, Greater) => looking.start = rate + 1,
-            (Musical, Less) => musical.end = rate,
-            (Musical, Greater) => musical.start = rate + 1,
-            (Aerodynamic, Less) => aerodynamic.end = rate,
-            (Aerodynamic, Greater) => aerodynamic.start = rate + 1,
-            (Shiny, Less) => shiny.end = rate,
-            (Shiny, Greater) => shiny.start = rate + 1,
-        }
-        self
+impl Puzzle for Solution {
+    type Parsed<'a> = Compiled;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Compiled {
+        let solver = Solver::from(input);
+        let tree = solver.compile();
+        Compiled { solver, tree }
     }
-}
 
-impl Condition {
-    fn reverse(&self) -> Self {
-        use Operator::*;
-        let category = self.category;
-        let operator = &self.operator;
-        let rate = self.rate;
-        match operator {
-            Less => Self {
-                category,
-                operator: Greater,
-                rate: rate - 1,
-            },
-            Greater => Self {
-                category,
-                operator: Less,
-                rate: rate + 1,
-            },
-        }
+    fn diagnostic(compiled: &Compiled) -> Option<String> {
+        compiled.tree.as_ref().err().cloned()
     }
-}
 
-impl Solver {
-    fn get_accepted_segments(
-        &self,
-        segment: Segment,
-        work_flow: &Workflow,
-        rule_index: usize,
-    ) -> Vec<Segment> {
-        use Destination::*;
-        use Finished::*;
-        let rule = &work_flow.rules[rule_index];
-        match (&rule.condition, &rule.destination) {
-            (None, End(Rejected)) => vec![],
-            (None, End(Accepted)) => vec![segment],
-            (None, Workflow(next_flow_name)) => {
-                self.get_accepted_segments(segment, &self.workflows[next_flow_name], 0)
-            }
-            (Some(condition), End(Accepted)) => [
-                vec![segment.clone().update_segment(condition)],
-                self.get_accepted_segments(
-                    segment.update_segment(&condition.reverse()),
-                    work_flow,
-                    rule_index + 1,
-                ),
-            ]
-            .concat(),
-            (Some(condition), End(Rejected)) => self.get_accepted_segments(
-                segment.update_segment(&condition.reverse()),
-                work_flow,
-                rule_index + 1,
-            ),
-            (Some(condition), Workflow(next_flow_name)) => {
-                let next_workflow_segments = self.get_accepted_segments(
-                    segment.clone().update_segment(condition),
-                    &self.workflows[next_flow_name],
-                    0,
-                );
-                let next_rule_segments = self.get_accepted_segments(
-                    segment.update_segment(&condition.reverse()),
-                    work_flow,
-                    rule_index + 1,
-                );
-                [next_workflow_segments, next_rule_segments].concat()
-            }
-        }
+    fn part_one(compiled: &Compiled) -> Option<usize> {
+        Some(compiled.solver.rating_sum(compiled.tree()?))
     }
-}
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let solver = Solver::from(input);
-    let first_workflow = solver.first_workflow();
-    let segments = solver.get_accepted_segments(Default::default(), first_workflow, 0);
-    Some(
-        segments
-            .iter()
-            .map(|segment| segment.distinct_combinations())
-            .sum(),
-    )
+    fn part_two(compiled: &Compiled) -> Option<usize> {
+        Some(
+            compiled
+                .tree()?
+                .count_accepted(compiled.solver.block(1..4001)),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -407,4 +485,56 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(167409079868000));
     }
+
+    #[test]
+    fn test_custom_bounds() {
+        let solver = Solver::from(&*advent_of_code::template::read_file("examples", DAY));
+        let tree = solver.compile().unwrap();
+        let bounds = 1990..2010;
+
+        let brute_force = (0..bounds.len().pow(4))
+            .map(|index| Part {
+                ratings: (0..4)
+                    .map(|category| {
+                        bounds.start + index / bounds.len().pow(category) % bounds.len()
+                    })
+                    .collect(),
+            })
+            .filter(|part| tree.classify(part) == Finished::Accepted)
+            .count();
+        assert_eq!(tree.count_accepted(solver.block(bounds)), brute_force);
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let solver = Solver::from("in{x>10:a,R}\na{x<5:R,m>0:b,A}\nb{s<3:in,A}\n\n{x=1}");
+        assert_eq!(
+            solver.find_cycle(),
+            Some(vec!["in", "a", "b"].into_iter().map(String::from).collect())
+        );
+        assert_eq!(
+            solver.compile().err(),
+            Some("Workflows loop: in -> a -> b".to_string())
+        );
+        assert_eq!(
+            part_one("in{x>10:a,R}\na{x<5:R,m>0:b,A}\nb{s<3:in,A}\n\n{x=1}"),
+            None
+        );
+
+        let input = "in{x>10:R,A}\nx{y}\ny{x}\n\n{x=1,m=2}";
+        assert_eq!(Solver::from(input).find_cycle(), None);
+        assert_eq!(part_one(input), Some(3));
+
+        let solver = Solver::from("in{x>10:a,R}\na{x<5:R,m>0:A,A}\nc{A}\n\n{x=1}");
+        assert_eq!(solver.find_cycle(), None);
+        assert_eq!(
+            solver.unreachable_rules(solver.block(1..4001)),
+            vec![
+                ("a".to_string(), 0),
+                ("a".to_string(), 2),
+                ("c".to_string(), 0)
+            ]
+        );
+        assert_eq!(solver.unreachable_rules(solver.block(1..11)).len(), 5);
+    }
 }
@@ -1,11 +1,10 @@
 use std::{
-    cell::RefCell,
     collections::{HashMap, VecDeque},
-    fmt::Display,
-    rc::{Rc, Weak},
-    vec,
+    fmt::{Display, Write},
+    io,
 };
 
+use advent_of_code::{visualize::export_text, Puzzle};
 use num::integer::lcm;
 
 advent_of_code::solution!(20);
@@ -14,6 +13,7 @@ const BROADCASTER_NAME: &str = "broadcaster";
 const BUTTON_NAME: &str = "button";
 const FLIP_FLOP_PREFIX: &str = "%";
 const CONJUNCTION_PREFIX: &str = "&";
+const FINAL_MACHINE_NAME: &str = "rx";
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 enum Pulse {
@@ -21,20 +21,6 @@ enum Pulse {
     High,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
-enum State {
-    On,
-    Off,
-}
-
-#[derive(Clone, Copy, PartialEq, Debug)]
-enum ModuleKind {
-    Untyped,
-    FlipFlop,
-    Conjunction,
-    Broadcast,
-}
-
 impl Display for Pulse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -44,446 +30,308 @@ impl Display for Pulse {
     }
 }
 
-type Address = String;
-
-trait Module {
-    fn get_address(&self) -> Address;
-    fn add_destination(&mut self, other: Weak<RefCell<dyn Module>>);
-    fn get_destinations(&self) -> Vec<Weak<RefCell<dyn Module>>>;
-    fn get_output_pulse(&self) -> Option<Pulse>;
-    fn push_history(&mut self, pulse: Pulse);
-    fn get_history(&self) -> Vec<Pulse>;
-
-    fn receive(&mut self, _from: Address, _pulse: Pulse) {}
-
-    fn connect_from(&mut self, _from: Address) {}
+/// The index of a module in its [`Network`].
+type ModuleId = usize;
 
-    fn is_enable(&self) -> bool {
-        true
-    }
-
-    fn connect_to(&mut self, to: Weak<RefCell<dyn Module>>) {
-        self.add_destination(to.clone());
-        to.upgrade()
-            .unwrap()
-            .borrow_mut()
-            .connect_from(self.get_address());
-    }
-
-    fn send(&mut self, pulse: Pulse) {
-        self.get_destinations().into_iter().for_each(|module| {
-            self.push_history(pulse);
-            let from_address = self.get_address();
-            module
-                .upgrade()
-                .unwrap()
-                .borrow_mut()
-                .receive(from_address, pulse);
-        });
-    }
-}
-
-impl State {
-    fn flip(&self) -> Self {
-        match self {
-            Self::On => Self::Off,
-            Self::Off => Self::On,
-        }
-    }
+#[derive(Clone, PartialEq, Debug)]
+enum ModuleKind {
+    Untyped,
+    FlipFlop {
+        on: bool,
+    },
+    /// Remembers the last pulse from every source, in the order of `sources`.
+    Conjunction {
+        memory: Vec<Pulse>,
+        high_count: usize,
+    },
+    Broadcast,
 }
 
-struct Untyped {
+#[derive(Clone)]
+struct Module {
     name: String,
-    output_pulse: Option<Pulse>,
-    destinations: Vec<Weak<RefCell<dyn Module>>>,
-    history: Vec<Pulse>,
-}
-
-struct FlipFlop {
-    base: Untyped,
-    state: State,
-    enabled: bool,
+    kind: ModuleKind,
+    sources: Vec<ModuleId>,
+    destinations: Vec<ModuleId>,
 }
 
-struct Conjunction {
-    base: Untyped,
-    last_pulse: HashMap<String, Pulse>,
+struct Event {
+    from: ModuleId,
+    to: ModuleId,
+    pulse: Pulse,
 }
 
-struct Broadcast {
-    base: Untyped,
+/// Watches every pulse sent while pushing the button.
+trait Observer {
+    fn observe(&mut self, presses: usize, event: &Event);
 }
 
-impl Untyped {
-    fn set_output_pulse(&mut self, pulse: Pulse) {
-        self.output_pulse = Some(pulse);
+impl<O: Observer> Observer for Vec<O> {
+    fn observe(&mut self, presses: usize, event: &Event) {
+        self.iter_mut()
+            .for_each(|observer| observer.observe(presses, event));
     }
 }
 
-impl Module for Untyped {
-    fn get_address(&self) -> Address {
-        self.name.clone()
-    }
-
-    fn add_destination(&mut self, other: Weak<RefCell<dyn Module>>) {
-        self.destinations.push(other);
-    }
-
-    fn get_destinations(&self) -> Vec<Weak<RefCell<dyn Module>>> {
-        self.destinations.clone()
-    }
-
-    fn get_output_pulse(&self) -> Option<Pulse> {
-        self.output_pulse
-    }
-
-    fn push_history(&mut self, pulse: Pulse) {
-        self.history.push(pulse);
-    }
-
-    fn get_history(&self) -> Vec<Pulse> {
-        self.history.clone()
-    }
+#[derive(Default)]
+struct PulseCounter {
+    low: usize,
+    high: usize,
 }
 
-impl Module for FlipFlop {
-    fn get_address(&self) -> Address {
-        self.base.get_address()
-    }
-
-    fn add_destination(&mut self, other: Weak<RefCell<dyn Module>>) {
-        self.base.add_destination(other);
-    }
-
-    fn get_destinations(&self) -> Vec<Weak<RefCell<dyn Module>>> {
-        self.base.get_destinations()
-    }
-
-    fn receive(&mut self, _from: Address, pulse: Pulse) {
-        use Pulse::*;
-        use State::*;
-        match (pulse, &self.state) {
-            (High, _) => self.enabled = false,
-            (Low, On) => {
-                self.state = self.state.flip();
-                self.base.set_output_pulse(Low);
-                self.enabled = true;
-            }
-            (Low, Off) => {
-                self.state = self.state.flip();
-                self.base.set_output_pulse(High);
-                self.enabled = true;
-            }
+impl Observer for PulseCounter {
+    fn observe(&mut self, _presses: usize, event: &Event) {
+        match event.pulse {
+            Pulse::Low => self.low += 1,
+            Pulse::High => self.high += 1,
         }
     }
-
-    fn get_output_pulse(&self) -> Option<Pulse> {
-        self.base.get_output_pulse()
-    }
-
-    fn push_history(&mut self, pulse: Pulse) {
-        self.base.push_history(pulse);
-    }
-
-    fn get_history(&self) -> Vec<Pulse> {
-        self.base.get_history()
-    }
-
-    fn is_enable(&self) -> bool {
-        self.enabled
-    }
 }
 
-impl Module for Conjunction {
-    fn get_address(&self) -> Address {
-        self.base.get_address()
-    }
-
-    fn add_destination(&mut self, other: Weak<RefCell<dyn Module>>) {
-        self.base.add_destination(other);
-    }
-
-    fn get_destinations(&self) -> Vec<Weak<RefCell<dyn Module>>> {
-        self.base.get_destinations()
-    }
-
-    fn connect_from(&mut self, from: Address) {
-        self.base.connect_from(from.clone());
-        self.last_pulse.insert(from, Pulse::Low);
-    }
-
-    fn receive(&mut self, from: Address, pulse: Pulse) {
-        self.last_pulse.insert(from, pulse);
-        use Pulse::*;
-        let output_pulse = match self.last_pulse.values().all(|pulse| *pulse == High) {
-            true => Low,
-            false => High,
-        };
-        self.base.set_output_pulse(output_pulse);
-    }
-
-    fn get_output_pulse(&self) -> Option<Pulse> {
-        self.base.get_output_pulse()
-    }
-
-    fn push_history(&mut self, pulse: Pulse) {
-        self.base.push_history(pulse);
-    }
-
-    fn get_history(&self) -> Vec<Pulse> {
-        self.base.get_history()
-    }
+/// Records the press during which `from` first sends a high pulse to `to`.
+struct FirstHigh {
+    from: ModuleId,
+    to: ModuleId,
+    press: Option<usize>,
 }
 
-impl Module for Broadcast {
-    fn get_address(&self) -> Address {
-        self.base.get_address()
-    }
-
-    fn add_destination(&mut self, other: Weak<RefCell<dyn Module>>) {
-        self.base.add_destination(other);
-    }
-
-    fn get_destinations(&self) -> Vec<Weak<RefCell<dyn Module>>> {
-        self.base.get_destinations()
-    }
-
-    fn receive(&mut self, _from: Address, pulse: Pulse) {
-        self.base.set_output_pulse(pulse);
-    }
-
-    fn get_output_pulse(&self) -> Option<Pulse> {
-        self.base.get_output_pulse()
-    }
-
-    fn push_history(&mut self, pulse: Pulse) {
-        self.base.push_history(pulse);
-    }
-
-    fn get_history(&self) -> Vec<Pulse> {
-        self.base.get_history()
+impl Observer for FirstHigh {
+    fn observe(&mut self, presses: usize, event: &Event) {
+        if self.press.is_none()
+            && event.pulse == Pulse::High
+            && (event.from, event.to) == (self.from, self.to)
+        {
+            self.press = Some(presses);
+        }
     }
 }
 
-impl Untyped {
-    fn new(name: &str) -> Self {
+impl Module {
+    fn new(name: &str, kind: ModuleKind) -> Self {
         Self {
             name: name.to_string(),
-            output_pulse: None,
+            kind,
+            sources: vec![],
             destinations: vec![],
-            history: vec![],
         }
     }
-}
 
-impl FlipFlop {
-    fn new(name: &str) -> Self {
-        Self {
-            state: State::Off,
-            base: Untyped::new(name),
-            enabled: false,
+    /// The pulse sent to every destination in response to `pulse` from `from`.
+    fn receive(&mut self, from: ModuleId, pulse: Pulse) -> Option<Pulse> {
+        use ModuleKind::*;
+        use Pulse::*;
+        match &mut self.kind {
+            Untyped => None,
+            Broadcast => Some(pulse),
+            FlipFlop { .. } if pulse == High => None,
+            FlipFlop { on } => {
+                *on = !*on;
+                Some(if *on { High } else { Low })
+            }
+            Conjunction { memory, high_count } => {
+                let index = self.sources.iter().position(|&source| source == from)?;
+                match (memory[index], pulse) {
+                    (Low, High) => *high_count += 1,
+                    (High, Low) => *high_count -= 1,
+                    _ => {}
+                }
+                memory[index] = pulse;
+                Some(if *high_count == memory.len() {
+                    Low
+                } else {
+                    High
+                })
+            }
         }
     }
 }
 
-impl Conjunction {
-    fn new(name: &str) -> Self {
-        Self {
-            base: Untyped::new(name),
-            last_pulse: HashMap::new(),
-        }
+/// The module names and kinds as written in the configuration.
+fn parse_module(value: &str) -> (&str, ModuleKind) {
+    use ModuleKind::*;
+    if value == BROADCASTER_NAME {
+        (value, Broadcast)
+    } else if let Some(name) = value.strip_prefix(FLIP_FLOP_PREFIX) {
+        (name, FlipFlop { on: false })
+    } else if let Some(name) = value.strip_prefix(CONJUNCTION_PREFIX) {
+        (
+            name,
+            Conjunction {
+                memory: vec![],
+                high_count: 0,
+            },
+        )
+    } else {
+        (value, Untyped)
     }
 }
 
-impl Broadcast {
-    fn new(name: &str) -> Self {
-        Self {
-            base: Untyped::new(name),
-        }
-    }
+#[derive(Clone)]
+struct Network {
+    modules: Vec<Module>,
+    ids: HashMap<String, ModuleId>,
+    presses: usize,
 }
 
-struct Solver {
-    modules: HashMap<Address, Rc<RefCell<dyn Module>>>,
-}
+impl From<&str> for Network {
+    fn from(value: &str) -> Self {
+        let mut network = Self {
+            modules: vec![],
+            ids: HashMap::new(),
+            presses: 0,
+        };
 
-impl Solver {
-    fn new(modules: HashMap<Address, Rc<RefCell<dyn Module>>>) -> Self {
-        Self { modules }
+        let connections: Vec<(ModuleId, &str)> = value
+            .lines()
+            .map(|line| {
+                let (module, destinations) = line.split_once(" -> ").unwrap();
+                let (name, kind) = parse_module(module);
+                (network.add_module(name, kind), destinations)
+            })
+            .collect();
+
+        let button = network.add_module(BUTTON_NAME, ModuleKind::Untyped);
+        let broadcaster = network.id(BROADCASTER_NAME);
+        network.connect(button, broadcaster);
+        connections.into_iter().for_each(|(from, destinations)| {
+            destinations.split(", ").for_each(|name| {
+                let to = network.id(name);
+                network.connect(from, to);
+            });
+        });
+
+        network
     }
+}
 
-    fn get_module(&self, address: &Address) -> Rc<RefCell<dyn Module>> {
-        self.modules.get(address).unwrap().clone()
+impl Network {
+    fn add_module(&mut self, name: &str, kind: ModuleKind) -> ModuleId {
+        let id = self.modules.len();
+        self.modules.push(Module::new(name, kind));
+        self.ids.insert(name.to_string(), id);
+        id
     }
 
-    fn get_button(&self) -> Rc<RefCell<dyn Module>> {
-        self.get_module(&BUTTON_NAME.to_string())
+    /// The module named `name`, adding an untyped one if there is none yet.
+    fn id(&mut self, name: &str) -> ModuleId {
+        match self.ids.get(name) {
+            Some(&id) => id,
+            None => self.add_module(name, ModuleKind::Untyped),
+        }
     }
 
-    fn get_history(&self, address: &Address) -> Vec<Pulse> {
-        self.get_module(address).borrow().get_history()
+    fn connect(&mut self, from: ModuleId, to: ModuleId) {
+        self.modules[from].destinations.push(to);
+        let module = &mut self.modules[to];
+        module.sources.push(from);
+        if let ModuleKind::Conjunction { memory, .. } = &mut module.kind {
+            memory.push(Pulse::Low);
+        }
     }
 
-    fn push_button(&self) {
-        self.get_button().borrow_mut().send(Pulse::Low);
-        let mut modules = VecDeque::from_iter(self.get_button().borrow().get_destinations());
-        while let Some(module) = modules.pop_front() {
-            if let Some(pulse) = {
-                let upgrade = module.upgrade().unwrap();
-                let borrowed = upgrade.borrow();
-                borrowed.get_output_pulse()
-            } {
-                module.upgrade().unwrap().borrow_mut().send(pulse);
-                modules.append(&mut VecDeque::from_iter(
-                    module
-                        .upgrade()
-                        .unwrap()
-                        .borrow()
-                        .get_destinations()
-                        .into_iter()
-                        .filter(|module| module.upgrade().unwrap().borrow().is_enable()),
-                ));
+    fn push_button(&mut self, observer: &mut impl Observer) {
+        self.presses += 1;
+        let mut events = VecDeque::from([Event {
+            from: self.ids[BUTTON_NAME],
+            to: self.ids[BROADCASTER_NAME],
+            pulse: Pulse::Low,
+        }]);
+
+        while let Some(event) = events.pop_front() {
+            observer.observe(self.presses, &event);
+            let module = &mut self.modules[event.to];
+            if let Some(pulse) = module.receive(event.from, event.pulse) {
+                events.extend(module.destinations.iter().map(|&to| Event {
+                    from: event.to,
+                    to,
+                    pulse,
+                }));
             }
         }
     }
 
-    fn solve_part_one(&self) -> Option<usize> {
-        use Pulse::*;
-        (0..1000).for_each(|_| self.push_button());
-        let (low_count, hight_count) = self
-            .modules
-            .values()
-            .flat_map(|module| module.borrow().get_history())
-            .fold((0, 0), |mut acc, pulse| {
-                match pulse {
-                    Low => acc.0 += 1,
-                    High => acc.1 += 1,
-                }
-                acc
+    /// The module graph in the Graphviz DOT language.
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        self.modules.iter().for_each(|module| {
+            let shape = match module.kind {
+                ModuleKind::Untyped => "plaintext",
+                ModuleKind::FlipFlop { .. } => "box",
+                ModuleKind::Conjunction { .. } => "diamond",
+                ModuleKind::Broadcast => "doublecircle",
+            };
+            writeln!(dot, "  \"{}\" [shape={}];", module.name, shape).unwrap();
+        });
+        self.modules.iter().for_each(|module| {
+            module.destinations.iter().for_each(|&to| {
+                writeln!(
+                    dot,
+                    "  \"{}\" -> \"{}\";",
+                    module.name, self.modules[to].name
+                )
+                .unwrap();
             });
-        Some(low_count * hight_count)
+        });
+        dot.push_str("}\n");
+        dot
     }
 
-    fn solve_part_two(&self) -> Option<usize> {
-        let mut cycles: HashMap<Address, Option<usize>> = HashMap::from_iter([
-            ("rk".to_string(), None),
-            ("cd".to_string(), None),
-            ("zf".to_string(), None),
-            ("qx".to_string(), None),
-        ]);
-        let mut count = 0;
-        loop {
-            self.push_button();
-            count += 1;
-            cycles
-                .iter_mut()
-                .filter(|(_, cycle)| cycle.is_none())
-                .for_each(|(address, cycle)| {
-                    if self.get_history(address).contains(&Pulse::High) {
-                        *cycle = Some(count);
-                    }
-                });
-
-            if cycles.values().all(Option::is_some) {
-                return cycles.into_values().map(Option::unwrap).reduce(lcm);
-            }
-        }
+    fn solve_part_one(&mut self) -> usize {
+        let mut counter = PulseCounter::default();
+        (0..1000).for_each(|_| self.push_button(&mut counter));
+        counter.low * counter.high
     }
-}
 
-struct ModuleInfo {
-    name: String,
-    kind: ModuleKind,
-}
-
-impl From<&str> for ModuleInfo {
-    fn from(value: &str) -> Self {
-        use ModuleKind::*;
-        if value == BROADCASTER_NAME {
-            Self {
-                name: value.to_string(),
-                kind: Broadcast,
-            }
-        } else if value.starts_with(FLIP_FLOP_PREFIX) {
-            Self {
-                name: value.strip_prefix(FLIP_FLOP_PREFIX).unwrap().to_string(),
-                kind: FlipFlop,
-            }
-        } else if value.starts_with(CONJUNCTION_PREFIX) {
-            Self {
-                name: value.strip_prefix(CONJUNCTION_PREFIX).unwrap().to_string(),
-                kind: Conjunction,
-            }
-        } else {
-            Self {
-                name: value.to_string(),
-                kind: Untyped,
-            }
+    /// The final machine is fed by a single conjunction, which sends it a low
+    /// pulse once all of its sources sent high ones during the same press.
+    /// Every source does so periodically, from its first high pulse on.
+    fn solve_part_two(&mut self) -> Option<usize> {
+        let &final_machine = self.ids.get(FINAL_MACHINE_NAME)?;
+        let &[feeder] = &self.modules[final_machine].sources[..] else {
+            return None;
+        };
+        let mut watchers: Vec<FirstHigh> = self.modules[feeder]
+            .sources
+            .iter()
+            .map(|&from| FirstHigh {
+                from,
+                to: feeder,
+                press: None,
+            })
+            .collect();
+
+        while watchers.iter().any(|watcher| watcher.press.is_none()) {
+            self.push_button(&mut watchers);
         }
+        watchers
+            .into_iter()
+            .map(|watcher| watcher.press.unwrap())
+            .reduce(lcm)
     }
 }
 
-fn create_module(module_info: ModuleInfo) -> Rc<RefCell<dyn Module>> {
-    match module_info.kind {
-        ModuleKind::Untyped => Rc::new(RefCell::new(Untyped::new(&module_info.name))),
-        ModuleKind::FlipFlop => Rc::new(RefCell::new(FlipFlop::new(&module_info.name))),
-        ModuleKind::Conjunction => Rc::new(RefCell::new(Conjunction::new(&module_info.name))),
-        ModuleKind::Broadcast => Rc::new(RefCell::new(Broadcast::new(&module_info.name))),
-    }
-}
-
-impl From<&str> for Solver {
-    fn from(value: &str) -> Self {
-        let mut modules: HashMap<Address, Rc<RefCell<dyn Module>>> = HashMap::new();
+struct Solution;
 
-        // initialize modules
-        value
-            .lines()
-            .map(|line| line.split(" -> ").next().unwrap())
-            .for_each(|module_info| {
-                let module = create_module(module_info.into());
-                modules.insert(module.clone().borrow().get_address(), module);
-            });
-
-        let mut get_or_create_module = |module_info: ModuleInfo| {
-            modules
-                .entry(module_info.name.clone())
-                .or_insert(create_module(module_info))
-                .clone()
-        };
+impl Puzzle for Solution {
+    type Parsed<'a> = Network;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-        // connect the modules
-        value.lines().for_each(|line| {
-            let mut parts = line.split(" -> ");
-            let module_info: ModuleInfo = parts.next().unwrap().into();
-            let module = get_or_create_module(module_info);
-
-            parts
-                .next()
-                .unwrap()
-                .split(", ")
-                .for_each(|connect_io_info| {
-                    let to_module_info: ModuleInfo = connect_io_info.into();
-                    let to_module = Rc::downgrade(&get_or_create_module(to_module_info));
-                    module.borrow_mut().connect_to(to_module);
-                });
-        });
-
-        // connect button to broadcaster
-        let button = get_or_create_module(ModuleInfo::from(BUTTON_NAME));
-        let broadcaster = get_or_create_module(ModuleInfo::from(BROADCASTER_NAME));
-        button.borrow_mut().connect_to(Rc::downgrade(&broadcaster));
+    fn parse(input: &str) -> Network {
+        Network::from(input)
+    }
 
-        Self::new(modules)
+    fn part_one(network: &Network) -> Option<usize> {
+        Some(network.clone().solve_part_one())
     }
-}
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Solver::from(input).solve_part_one()
-}
+    fn part_two(network: &Network) -> Option<usize> {
+        network.clone().solve_part_two()
+    }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    Solver::from(input).solve_part_two()
+    fn export(network: &Network) -> io::Result<()> {
+        export_text("20.dot", &network.to_dot())
+    }
 }
 
 #[cfg(test)]
@@ -510,4 +358,36 @@ mod tests {
         ));
         assert_eq!(result, Some(226732077152351));
     }
+
+    #[test]
+    fn test_single_press() {
+        let mut network = Network::from(&*advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        let mut counter = PulseCounter::default();
+        network.push_button(&mut counter);
+        assert_eq!((counter.low, counter.high), (4, 4));
+
+        let output = network.ids["output"];
+        let mut watcher = vec![FirstHigh {
+            from: network.ids["con"],
+            to: output,
+            press: None,
+        }];
+        (0..3).for_each(|_| network.push_button(&mut watcher));
+        assert_eq!(watcher[0].press, Some(2));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let network = Network::from(&*advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        let dot = network.to_dot();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("  \"inv\" [shape=diamond];\n"));
+        assert!(dot.contains("  \"a\" [shape=box];\n"));
+        assert!(dot.contains("  \"button\" -> \"broadcaster\";\n"));
+        assert!(dot.contains("  \"con\" -> \"output\";\n"));
+    }
 }
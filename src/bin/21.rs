@@ -1,6 +1,6 @@
 use std::collections::{HashMap, VecDeque};
 
-use advent_of_code::{Matrix, Position as P};
+use advent_of_code::{newton_forward, Matrix, Position as P, Puzzle};
 
 advent_of_code::solution!(21);
 
@@ -114,32 +114,44 @@ impl Garden {
 
     // The garden plots and rocks are set up so that the map repeats infinitely
     // in every direction.
-    fn crazy_steps(&self) -> usize {
+    fn crazy_steps(&self) -> Option<usize> {
         let map = self.get_distance_map(328);
 
         // Exploiting some nice properties of the input it reduces to quadratic
         // interpolation over 3 points: k * 131 + 65 for k = 0, 1, 2
-        // https://en.wikipedia.org/wiki/Newton_polynomial
-        let (x0, y0) = (65_f64, Garden::marked_count(65, &map) as f64);
-        let (x1, y1) = (196_f64, Garden::marked_count(196, &map) as f64);
-        let (x2, y2) = (327_f64, Garden::marked_count(327, &map) as f64);
+        let counts = [65, 196, 327].map(|steps| Garden::marked_count(steps, &map));
+        extrapolate(&counts, (26501365 - 65) / 131)
+    }
+}
+
+/// The count after `repeats` repetitions of the map, from the counts after the
+/// first few; signed, as the differences between them may be negative.
+fn extrapolate(counts: &[usize], repeats: i64) -> Option<usize> {
+    let counts: Vec<i64> = counts
+        .iter()
+        .map(|&count| count.try_into().ok())
+        .collect::<Option<_>>()?;
+    newton_forward(&counts, repeats)?.try_into().ok()
+}
 
-        let a0 = y0;
-        let a1 = (y1 - y0) / (x1 - x0);
-        let a2 = (y2 - y1) / ((x2 - x1) * (x2 - x0)) - a1 / (x2 - x0);
+struct Solution;
 
-        let newton_polynomial = |x: f64| -> f64 { a0 + a1 * (x - x0) + a2 * (x - x0) * (x - x1) };
+impl Puzzle for Solution {
+    type Parsed<'a> = Garden;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-        newton_polynomial(26501365_f64) as usize
+    fn parse(input: &str) -> Garden {
+        Garden::from(input)
     }
-}
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(Garden::from(input).steps())
-}
+    fn part_one(garden: &Garden) -> Option<usize> {
+        Some(garden.steps())
+    }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    Some(Garden::from(input).crazy_steps())
+    fn part_two(garden: &Garden) -> Option<usize> {
+        garden.crazy_steps()
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +170,12 @@ mod tests {
         assert_eq!(result, Some(598044246091826));
     }
 
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(extrapolate(&[10, 8, 9], 3), Some(13));
+        assert_eq!(extrapolate(&[4, 2, 0], 3), None);
+    }
+
     #[test]
     fn test_cycle_to_range() {
         assert_eq!(cycle_to_range(-4, -1, 1), -1);
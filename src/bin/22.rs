@@ -1,50 +1,19 @@
-#![feature(binary_heap_into_iter_sorted)]
-
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap, HashSet},
-    fmt::Display,
-    ops::RangeInclusive,
-};
+use advent_of_code::{Axis, Cuboid, Position3, Puzzle, SortedDrain, VoxelGrid};
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Display};
 
 advent_of_code::solution!(22);
 
 #[derive(PartialEq, Eq, Hash, Clone)]
-struct Coordinate {
-    x: usize,
-    y: usize,
-    z: usize,
-}
-
-impl Display for Coordinate {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let Self { x, y, z } = self;
-        write!(f, "{},{},{}", x, y, z)
-    }
-}
-
-#[derive(PartialEq, Eq, Hash, Clone)]
-struct Brick {
-    start: Coordinate,
-    end: Coordinate,
-}
+struct Brick(Cuboid<usize>);
 
 impl Display for Brick {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let Self { start, end } = self;
-        write!(f, "{}~{}", start, end)
-    }
-}
-
-impl PartialOrd for Brick {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Brick {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.lowest().cmp(&other.lowest())
+        let Cuboid { min, max } = self.0;
+        write!(
+            f,
+            "{},{},{}~{},{},{}",
+            min.x, min.y, min.z, max.x, max.y, max.z
+        )
     }
 }
 
@@ -52,29 +21,20 @@ struct SandSlabs {
     bricks: Vec<Brick>,
 }
 
-impl From<(usize, usize, usize)> for Coordinate {
-    fn from(value: (usize, usize, usize)) -> Self {
-        let (x, y, z) = value;
-        Self { x, y, z }
-    }
-}
-
-impl From<&str> for Coordinate {
-    fn from(value: &str) -> Self {
-        let mut iter = value.split(',').map(|x| x.parse().unwrap());
-        let x = iter.next().unwrap();
-        let y = iter.next().unwrap();
-        let z = iter.next().unwrap();
-        Self::from((x, y, z))
-    }
+fn parse_position(value: &str) -> Position3<usize> {
+    let mut iter = value.split(',').map(|x| x.parse().unwrap());
+    let x = iter.next().unwrap();
+    let y = iter.next().unwrap();
+    let z = iter.next().unwrap();
+    Position3::new(x, y, z)
 }
 
 impl From<&str> for Brick {
     fn from(value: &str) -> Self {
-        let mut iter = value.split('~').map(Coordinate::from);
+        let mut iter = value.split('~').map(parse_position);
         let start = iter.next().unwrap();
         let end = iter.next().unwrap();
-        Self { start, end }
+        Self(Cuboid::new(start, end))
     }
 }
 
@@ -85,105 +45,141 @@ impl From<&str> for SandSlabs {
     }
 }
 
-struct FallResult {
+/// Settled bricks ordered from bottom to top, so every brick comes after the
+/// bricks supporting it.
+struct Settled {
     bricks: Vec<Brick>,
-    support: HashMap<Brick, Vec<Brick>>,
-    supported: HashMap<Brick, Vec<Brick>>,
+    supports: Vec<Vec<usize>>,
+    supported_by: Vec<Vec<usize>>,
 }
 
-#[derive(Eq, PartialEq, Clone)]
-struct HeapState {
-    brick: Brick,
-}
+impl SandSlabs {
+    fn settle(&self) -> Settled {
+        // lowest bricks first, ties in input order
+        let falling: BinaryHeap<_> = self
+            .bricks
+            .iter()
+            .enumerate()
+            .map(|(input, brick)| Reverse((brick.lowest(), input)))
+            .collect();
+
+        // the top cell of every xy column, tagged with the brick it belongs to
+        let mut height_map = VoxelGrid::new();
+        let mut settled: Vec<Brick> = Vec::with_capacity(self.bricks.len());
+        let mut supports = vec![vec![]; self.bricks.len()];
+        let mut supported_by = vec![vec![]; self.bricks.len()];
+
+        let order = falling.into_ordered_iter().map(|Reverse((_, input))| input);
+        order.enumerate().for_each(|(index, input)| {
+            let brick = &self.bricks[input];
+            let footprint = brick.0.projection(Axis::Z);
+            let tops: Vec<(usize, usize)> = footprint
+                .positions()
+                .filter_map(|Position3 { x, y, .. }| height_map.top(x, y))
+                .map(|(position, &below)| (position.z, below))
+                .collect();
+            let rest = tops.iter().map(|&(z, _)| z).max().unwrap_or_default();
+
+            let mut supporters: Vec<usize> = tops
+                .into_iter()
+                .filter(|&(z, _)| z == rest)
+                .map(|(_, below)| below)
+                .collect();
+            supporters.sort_unstable();
+            supporters.dedup();
+
+            let brick = brick.fall(brick.lowest() - rest - 1);
+            debug_assert!(supporters
+                .iter()
+                .all(|&below| settled[below].is_support(&brick)));
 
-impl From<Brick> for HeapState {
-    fn from(value: Brick) -> Self {
-        Self { brick: value }
-    }
-}
+            supporters
+                .iter()
+                .for_each(|&below| supports[below].push(index));
+            supported_by[index] = supporters;
+            footprint.positions().for_each(|Position3 { x, y, .. }| {
+                if let Some(z) = height_map.height(x, y) {
+                    height_map.remove(&Position3::new(x, y, z));
+                }
+                height_map.insert(Position3::new(x, y, brick.highest()), index);
+            });
+            settled.push(brick);
+        });
 
-impl PartialOrd for HeapState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+        Settled {
+            bricks: settled,
+            supports,
+            supported_by,
+        }
     }
 }
 
-impl Ord for HeapState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.brick.highest().cmp(&other.brick.highest())
+impl Settled {
+    fn is_safe(&self, index: usize) -> bool {
+        self.supports[index]
+            .iter()
+            .all(|&above| self.supported_by[above].len() >= 2)
     }
-}
 
-impl SandSlabs {
-    fn fall(&self) -> FallResult {
-        let mut bricks = self.bricks.clone();
-        let mut settled = BinaryHeap::new();
-        let mut support: HashMap<Brick, Vec<Brick>> = HashMap::new();
-        let mut supported: HashMap<Brick, Vec<Brick>> = HashMap::new();
-        bricks.sort();
-
-        fn fall_to_settle(brick: &Brick, settled: &BinaryHeap<HeapState>) -> Brick {
-            let mut new_settled_brick = None;
-            for HeapState {
-                brick: settled_brick,
-            } in settled.clone().into_iter_sorted()
-            {
-                if brick.is_intersect_in_xy(&settled_brick) {
-                    let diff = brick.lowest() - settled_brick.highest() - 1;
-                    new_settled_brick = Some(brick.fall(diff));
-                    break;
-                }
-            }
-            new_settled_brick.unwrap_or(brick.fall(brick.lowest() - 1))
-        }
+    /// Immediate dominators of the support graph rooted at the ground: the
+    /// nearest brick every path from the ground to a brick has to pass
+    /// through, or `None` if only the ground holds it up.
+    fn dominators(&self) -> Vec<Option<usize>> {
+        let mut dominators: Vec<Option<usize>> = Vec::with_capacity(self.bricks.len());
+        let mut depths: Vec<usize> = Vec::with_capacity(self.bricks.len());
+        let depth = |node: Option<usize>, depths: &[usize]| node.map_or(0, |node| depths[node]);
 
-        let mut update_dependencies = |brick: &Brick, settled: &BinaryHeap<HeapState>| {
-            settled.iter().for_each(
-                |HeapState {
-                     brick: settled_brick,
-                 }| {
-                    if settled_brick.is_support(brick) {
-                        support
-                            .entry(settled_brick.clone())
-                            .or_default()
-                            .push(brick.clone());
-                        supported
-                            .entry(brick.clone())
-                            .or_default()
-                            .push(settled_brick.clone());
+        (0..self.bricks.len()).for_each(|index| {
+            let dominator = self.supported_by[index]
+                .iter()
+                .map(|&below| Some(below))
+                .reduce(|mut a, mut b| {
+                    // lowest common ancestor in the dominator tree built so far
+                    while a != b {
+                        if depth(a, &depths) >= depth(b, &depths) {
+                            a = a.and_then(|node| dominators[node]);
+                        } else {
+                            b = b.and_then(|node| dominators[node]);
+                        }
                     }
-                },
-            );
-        };
-
-        bricks.iter().for_each(|brick| {
-            let settled_brick = fall_to_settle(brick, &settled);
-            update_dependencies(&settled_brick, &settled);
-            settled.push(HeapState::from(settled_brick));
+                    a
+                })
+                .flatten();
+
+            depths.push(depth(dominator, &depths) + 1);
+            dominators.push(dominator);
         });
 
-        FallResult {
-            bricks: settled.into_iter().map(|state| state.brick).collect(),
-            support,
-            supported,
-        }
+        dominators
+    }
+
+    /// The number of other bricks that fall if a brick is disintegrated,
+    /// i.e. the size of its subtree in the dominator tree.
+    fn chain_reactions(&self) -> Vec<usize> {
+        let dominators = self.dominators();
+        let mut fallings = vec![0; self.bricks.len()];
+
+        (0..self.bricks.len()).rev().for_each(|index| {
+            if let Some(dominator) = dominators[index] {
+                fallings[dominator] += fallings[index] + 1;
+            }
+        });
+
+        fallings
     }
 }
 
 impl Brick {
     fn fall(&self, diff: usize) -> Self {
-        let mut brick = self.clone();
-        brick.start.z -= diff;
-        brick.end.z -= diff;
-        brick
+        Self(self.0.with_min(Axis::Z, self.lowest() - diff))
     }
 
     fn highest(&self) -> usize {
-        self.start.z.max(self.end.z)
+        self.0.max.z
     }
 
     fn lowest(&self) -> usize {
-        self.start.z.min(self.end.z)
+        self.0.min.z
     }
 
     fn is_support(&self, other: &Self) -> bool {
@@ -191,79 +187,36 @@ impl Brick {
     }
 
     fn is_intersect_in_xy(&self, other: &Self) -> bool {
-        is_overlap(
-            create_range(self.start.x, self.end.x),
-            create_range(other.start.x, other.end.x),
-        ) && is_overlap(
-            create_range(self.start.y, self.end.y),
-            create_range(other.start.y, other.end.y),
-        )
+        self.0
+            .projection(Axis::Z)
+            .intersects(&other.0.projection(Axis::Z))
     }
 }
 
-fn create_range(start: usize, end: usize) -> RangeInclusive<usize> {
-    let min = start.min(end);
-    let max = start.max(end);
-    min..=max
-}
+struct Solution;
 
-fn is_overlap(range: RangeInclusive<usize>, other: RangeInclusive<usize>) -> bool {
-    let min1 = range.start();
-    let max1 = range.end();
-    let min2 = other.start();
-    let max2 = other.end();
-    min1.max(min2) <= max1.min(max2)
-}
-
-pub fn part_one(input: &str) -> Option<usize> {
-    let FallResult {
-        bricks,
-        support,
-        supported,
-    } = SandSlabs::from(input).fall();
-    let is_safe = |brick: &Brick| -> bool {
-        support.get(brick).map_or(true, |supports| {
-            supports
-                .iter()
-                .all(|brick| supported.get(brick).unwrap().len() >= 2)
-        })
-    };
-
-    Some(bricks.iter().filter(|brick| is_safe(brick)).count())
-}
+impl Puzzle for Solution {
+    type Parsed<'a> = Settled;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let FallResult {
-        bricks,
-        support,
-        supported,
-    } = SandSlabs::from(input).fall();
-
-    let should_falling = |brick: &Brick, fallings: &HashSet<&Brick>| {
-        supported
-            .get(brick)
-            .unwrap()
-            .iter()
-            .all(|brick| fallings.contains(brick))
-    };
-
-    let falling_count = |disintegrated: &Brick| -> usize {
-        let mut fallings = HashSet::new();
-        let mut disintegrated_queue = vec![disintegrated];
-        while let Some(disintegrated) = disintegrated_queue.pop() {
-            fallings.insert(disintegrated);
-            if let Some(supports) = support.get(disintegrated) {
-                supports
-                    .iter()
-                    .filter(|brick| should_falling(brick, &fallings))
-                    .for_each(|disintegrated| disintegrated_queue.push(disintegrated));
-            }
-        }
+    /// Lets the bricks fall while parsing, as both parts ask about where
+    /// they come to rest.
+    fn parse(input: &str) -> Settled {
+        SandSlabs::from(input).settle()
+    }
 
-        fallings.len() - 1
-    };
+    fn part_one(settled: &Settled) -> Option<usize> {
+        Some(
+            (0..settled.bricks.len())
+                .filter(|&index| settled.is_safe(index))
+                .count(),
+        )
+    }
 
-    Some(bricks.iter().map(falling_count).sum())
+    fn part_two(settled: &Settled) -> Option<usize> {
+        Some(settled.chain_reactions().iter().sum())
+    }
 }
 
 #[cfg(test)]
@@ -302,4 +255,11 @@ mod tests {
         assert!(e.is_intersect_in_xy(&f));
         assert!(f.is_intersect_in_xy(&g));
     }
+
+    #[test]
+    fn test_chain_reactions() {
+        let settled =
+            SandSlabs::from(&*advent_of_code::template::read_file("examples", DAY)).settle();
+        assert_eq!(settled.chain_reactions(), vec![6, 0, 0, 0, 0, 1, 0]);
+    }
 }
@@ -1,10 +1,14 @@
 use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
+    io,
     ops::Not,
 };
 
-use advent_of_code::{Direction, Matrix, Position as P};
+use advent_of_code::{
+    visualize::{Color, Frame, Recorder, Style},
+    Direction, Matrix, Position as P, Puzzle,
+};
 
 type Position = P<usize>;
 
@@ -39,6 +43,19 @@ impl From<char> for Tile {
     }
 }
 
+impl Tile {
+    fn symbol(&self) -> char {
+        match self {
+            Path => '.',
+            Forest => '#',
+            Slope(Up) => '^',
+            Slope(Right) => '>',
+            Slope(Down) => 'v',
+            Slope(Left) => '<',
+        }
+    }
+}
+
 impl From<&str> for Map {
     fn from(value: &str) -> Self {
         Self::new(Matrix::from(value))
@@ -142,6 +159,15 @@ impl Hike {
         cloned.push(*pos);
         Self(cloned)
     }
+
+    /// Every tile walked, from the start to the last node.
+    fn tiles(&self, map: &Map) -> Vec<Position> {
+        let mut tiles = vec![self.get_node(0)];
+        self.0
+            .windows(2)
+            .for_each(|nodes| tiles.append(&mut map.trail(nodes[0], nodes[1])));
+        tiles
+    }
 }
 
 impl Map {
@@ -235,17 +261,43 @@ impl Map {
         hikes
     }
 
-    fn longest_hike(&self) -> usize {
+    fn longest(&self) -> Hike {
         self.find_all_hikes()
-            .iter()
-            .map(|hike| hike.steps(&self.graph))
-            .max()
+            .into_iter()
+            .max_by_key(|hike| hike.steps(&self.graph))
             .unwrap()
     }
-}
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(Map::from(input).longest_hike())
+    fn longest_hike(&self) -> usize {
+        self.longest().steps(&self.graph)
+    }
+
+    /// The tiles walked from node `a` to the adjacent node `b`, without `a`.
+    fn trail(&self, a: Node, b: Node) -> Vec<Position> {
+        let distance = self.graph.get_distance(a, b);
+        Self::get_neighbors(&self.tiles, &a)
+            .into_iter()
+            .find_map(|first| {
+                let mut trail = vec![first];
+                let mut previous = a;
+                while !self.graph.adjacencies.contains_key(trail.last().unwrap()) {
+                    let current = *trail.last().unwrap();
+                    let next = Self::get_neighbors(&self.tiles, &current)
+                        .into_iter()
+                        .find(|&next| next != previous)?;
+                    previous = current;
+                    trail.push(next);
+                }
+                (trail.last() == Some(&b) && trail.len() == distance).then_some(trail)
+            })
+            .expect("Adjacent nodes should be joined by a trail.")
+    }
+
+    fn render(&self, hike: &Hike) -> Frame {
+        let mut frame = Frame::new(&self.tiles, |_, tile| tile.symbol());
+        frame.highlight(&hike.tiles(self), Style::background(Color::YELLOW));
+        frame
+    }
 }
 
 fn covert_map(map: &Map) -> Map {
@@ -257,8 +309,32 @@ fn covert_map(map: &Map) -> Map {
     Map::new(tiles)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    Some(covert_map(&Map::from(input)).longest_hike())
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Map;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Map {
+        Map::from(input)
+    }
+
+    fn part_one(map: &Map) -> Option<usize> {
+        Some(map.longest_hike())
+    }
+
+    fn part_two(map: &Map) -> Option<usize> {
+        Some(covert_map(map).longest_hike())
+    }
+
+    fn export(map: &Map) -> io::Result<()> {
+        let mut recorder = Recorder::from_env();
+        recorder.record(|| map.render(&map.longest()));
+        let map = covert_map(map);
+        recorder.record(|| map.render(&map.longest()));
+        recorder.save("23")
+    }
 }
 
 #[cfg(test)]
@@ -276,4 +352,17 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(154));
     }
+
+    #[test]
+    fn test_hike_tiles() {
+        let map = Solution::parse(&advent_of_code::template::read_file("examples", DAY));
+        for (map, steps) in [(covert_map(&map), 154), (map, 94)] {
+            let tiles = map.longest().tiles(&map);
+            assert_eq!(tiles.len(), steps + 1);
+            assert_eq!(tiles.iter().collect::<HashSet<_>>().len(), tiles.len());
+            assert!(tiles
+                .windows(2)
+                .all(|pair| pair[0].adjacent_positions().contains(&pair[1])));
+        }
+    }
 }
@@ -1,128 +1,245 @@
-use matrix::{
-    equation,
-    line::{Intersection, Line},
-    linear_system, vector, Solution, Vector,
-};
+use advent_of_code::{solve_linear_system, Position3, Puzzle};
+use itertools::Itertools;
+use num::{rational::Ratio, BigRational, FromPrimitive, Num, Signed, ToPrimitive};
 
 advent_of_code::solution!(24);
 
-#[derive(Clone, Copy)]
-struct Time {
-    nanosecond: f64,
+/// The number type a [`Hailstone`] is solved in: `f64` approximates, while
+/// `Ratio<i128>` and `BigRational` are exact. `Ratio<i128>` is fast enough for
+/// pairwise intersections, but may overflow while solving larger systems.
+trait Scalar: Num + Signed + FromPrimitive + PartialOrd + Clone {
+    /// The value as an integer, if it is (close enough to) one.
+    fn as_integer(&self) -> Option<i128>;
 }
 
-impl Time {
-    fn new(nanosecond: f64) -> Self {
-        Self { nanosecond }
+impl Scalar for f64 {
+    fn as_integer(&self) -> Option<i128> {
+        self.round().to_i128()
     }
 }
 
-struct Hailstone {
-    position: Vector<3>,
-    velocity: Vector<3>,
+impl Scalar for Ratio<i128> {
+    fn as_integer(&self) -> Option<i128> {
+        self.is_integer().then(|| self.to_integer())
+    }
+}
+
+impl Scalar for BigRational {
+    fn as_integer(&self) -> Option<i128> {
+        self.is_integer().then(|| self.to_integer().to_i128())?
+    }
+}
+
+#[derive(Clone)]
+struct Hailstone<T> {
+    position: Position3<T>,
+    velocity: Position3<T>,
 }
 
-impl From<&str> for Hailstone {
+impl<T> From<&str> for Hailstone<T>
+where
+    T: FromPrimitive,
+{
     fn from(value: &str) -> Self {
         let mut iter = value.split('@');
 
-        fn into_vector(value: &str) -> Vector<3> {
-            let mut iter = value.split(',').map(|x| x.trim().parse::<f64>().unwrap());
-            vector([
+        fn into_position<T: FromPrimitive>(value: &str) -> Position3<T> {
+            let mut iter = value
+                .split(',')
+                .map(|x| T::from_i64(x.trim().parse().unwrap()).unwrap());
+            Position3::new(
                 iter.next().unwrap(),
                 iter.next().unwrap(),
                 iter.next().unwrap(),
-            ])
+            )
         }
 
-        let position = into_vector(iter.next().unwrap());
-        let velocity = into_vector(iter.next().unwrap());
+        let position = into_position(iter.next().unwrap());
+        let velocity = into_position(iter.next().unwrap());
         Self { position, velocity }
     }
 }
 
-impl Hailstone {
-    fn get_line_in_xy(&self) -> Line {
-        let px = self.position[0];
-        let py = self.position[1];
-        let vx = self.velocity[0];
-        let vy = self.velocity[1];
-        let normal_vector = vector([vy, -vx]);
-        let constant_term = vy * px - vx * py;
-        equation(normal_vector, constant_term)
+impl<T> Hailstone<T>
+where
+    T: Scalar,
+{
+    /// The times at which both hailstones pass the crossing point of their
+    /// paths in the xy plane, or `None` for parallel paths.
+    fn intersect_in_xy(&self, other: &Self) -> Option<(T, T)> {
+        let Position3 { x: vx, y: vy, .. } = self.velocity.clone();
+        let Position3 {
+            x: other_vx,
+            y: other_vy,
+            ..
+        } = other.velocity.clone();
+        let Position3 { x: dx, y: dy, .. } = other.position.clone() - self.position.clone();
+
+        let determinant = other_vx.clone() * vy.clone() - vx.clone() * other_vy.clone();
+        if determinant.is_zero() {
+            return None;
+        }
+
+        let time = (other_vx * dy.clone() - other_vy * dx.clone()) / determinant.clone();
+        let other_time = (vx * dy - vy * dx) / determinant;
+        Some((time, other_time))
     }
 
-    fn intersect(&self, other: &Self) -> Intersection {
-        let line = self.get_line_in_xy();
-        let other_line = other.get_line_in_xy();
-        line.intersect(&other_line)
+    fn position_at(&self, time: &T) -> Position3<T> {
+        self.position.clone() + self.velocity.clone() * time.clone()
     }
 
-    fn position_at(&self, time: &Time) -> Vector<3> {
-        self.position + self.velocity * time.nanosecond
+    fn collide_in_area(&self, other: &Self, min: &T, max: &T) -> bool {
+        match self.intersect_in_xy(other) {
+            Some((time, other_time)) if time.is_positive() && other_time.is_positive() => {
+                let Position3 { x, y, .. } = self.position_at(&time);
+                min <= &x && &x <= max && min <= &y && &y <= max
+            }
+            _ => false,
+        }
     }
 
-    fn collide(&self, other: &Self) -> Option<(Time, Time, Vector<3>)> {
-        let time = match self.intersect(other) {
-            Intersection::Some(position) => Some((
-                Time::new((position[0] - self.position[0]) / self.velocity[0]),
-                Time::new((position[0] - other.position[0]) / other.velocity[0]),
-            )),
-            _ => None,
+    fn as_integer(&self) -> Option<Hailstone<i128>> {
+        let into_integer = |position: &Position3<T>| {
+            Some(Position3::new(
+                position.x.as_integer()?,
+                position.y.as_integer()?,
+                position.z.as_integer()?,
+            ))
         };
-        time.map(|(time, other_time)| {
-            let position = self.position_at(&time);
-            (time, other_time, position)
+        Some(Hailstone {
+            position: into_integer(&self.position)?,
+            velocity: into_integer(&self.velocity)?,
         })
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let hailstones: Vec<Hailstone> = input.lines().map(Hailstone::from).collect();
-    let mut intersections = 0;
-    (0..hailstones.len() - 1).for_each(|i| {
-        (i + 1..hailstones.len()).for_each(|j| {
-            if let Some((time, other_time, position)) = hailstones[i].collide(&hailstones[j]) {
-                if time.nanosecond > 0.
-                    && other_time.nanosecond > 0.
-                    && position[0] >= 200000000000000.
-                    && position[1] >= 200000000000000.
-                    && position[0] <= 400000000000000.
-                    && position[1] <= 400000000000000.
-                {
-                    intersections += 1;
+impl Hailstone<i128> {
+    /// The non-negative integer time at which `rock` hits this hailstone.
+    fn hit_time(&self, rock: &Self) -> Option<i128> {
+        let distance = self.position - rock.position;
+        let closing = rock.velocity - self.velocity;
+        let axes = [
+            (distance.x, closing.x),
+            (distance.y, closing.y),
+            (distance.z, closing.z),
+        ];
+
+        let mut time = None;
+        for (distance, closing) in axes {
+            if closing == 0 {
+                if distance != 0 {
+                    return None;
                 }
+                continue;
             }
-        })
-    });
-    Some(intersections)
+            if distance % closing != 0 || time.is_some_and(|time| time != distance / closing) {
+                return None;
+            }
+            time = Some(distance / closing);
+        }
+
+        // identical trajectories collide at any time
+        time.or(Some(0)).filter(|time| *time >= 0)
+    }
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+fn parse<T: FromPrimitive>(input: &str) -> Vec<Hailstone<T>> {
+    input.lines().map(Hailstone::from).collect()
+}
+
+fn count_intersections<T: Scalar>(hailstones: &[Hailstone<T>], min: i64, max: i64) -> usize {
+    let min = T::from_i64(min).unwrap();
+    let max = T::from_i64(max).unwrap();
+    hailstones
+        .iter()
+        .tuple_combinations()
+        .filter(|(a, b)| a.collide_in_area(b, &min, &max))
+        .count()
+}
+
+/// Finds the rock that hits every hailstone, verified to do so at integer times.
+fn throw_rock<T: Scalar>(hailstones: &[Hailstone<T>]) -> Option<Hailstone<i128>> {
+    if hailstones.len() < 3 {
+        return None;
+    }
     // (p - p[i]) x (v - v[i]) = 0
     // https://typst.app/project/rDEObAUN5ac0NsNYoFnSI7
-    let hailstones: Vec<Hailstone> = input.lines().map(Hailstone::from).collect();
-    let v01 = hailstones[0].velocity - hailstones[1].velocity;
-    let p10 = hailstones[1].position - hailstones[0].position;
-    let c01 = hailstones[1].position.cross(&hailstones[1].velocity)
-        - hailstones[0].position.cross(&hailstones[0].velocity);
-    let v12 = hailstones[1].velocity - hailstones[2].velocity;
-    let p21 = hailstones[2].position - hailstones[1].position;
-    let c12 = hailstones[2].position.cross(&hailstones[2].velocity)
-        - hailstones[1].position.cross(&hailstones[1].velocity);
-
-    let system = linear_system([
-        equation(vector([0., -v01[2], v01[1], 0., -p10[2], p10[1]]), c01[0]),
-        equation(vector([v01[2], 0., -v01[0], p10[2], 0., -p10[0]]), c01[1]),
-        equation(vector([-v01[1], v01[0], 0., -p10[1], p10[0], 0.]), c01[2]),
-        equation(vector([0., -v12[2], v12[1], 0., -p21[2], p21[1]]), c12[0]),
-        equation(vector([v12[2], 0., -v12[0], p21[2], 0., -p21[0]]), c12[1]),
-        equation(vector([-v12[1], v12[0], 0., -p21[1], p21[0], 0.]), c12[2]),
-    ]);
-
-    match system.compute_solution() {
-        Solution::Some(s) => Some(s[0] as usize + s[1] as usize + s[2] as usize),
-        _ => None,
+    let cross = |hailstone: &Hailstone<T>| hailstone.position.cross(&hailstone.velocity);
+    let v01 = hailstones[0].velocity.clone() - hailstones[1].velocity.clone();
+    let p10 = hailstones[1].position.clone() - hailstones[0].position.clone();
+    let c01 = cross(&hailstones[1]) - cross(&hailstones[0]);
+    let v12 = hailstones[1].velocity.clone() - hailstones[2].velocity.clone();
+    let p21 = hailstones[2].position.clone() - hailstones[1].position.clone();
+    let c12 = cross(&hailstones[2]) - cross(&hailstones[1]);
+
+    let rows = |v: Position3<T>, p: Position3<T>| {
+        let zero = T::zero;
+        vec![
+            vec![
+                zero(),
+                -v.z.clone(),
+                v.y.clone(),
+                zero(),
+                -p.z.clone(),
+                p.y.clone(),
+            ],
+            vec![
+                v.z.clone(),
+                zero(),
+                -v.x.clone(),
+                p.z.clone(),
+                zero(),
+                -p.x.clone(),
+            ],
+            vec![-v.y, v.x, zero(), -p.y, p.x, zero()],
+        ]
+    };
+    let coefficients = [rows(v01, p10), rows(v12, p21)].concat();
+    let constants = vec![c01.x, c01.y, c01.z, c12.x, c12.y, c12.z];
+
+    let solution = solve_linear_system(coefficients, constants)?;
+    let mut solution = solution.into_iter();
+    let mut next = || solution.next().unwrap();
+    let rock = Hailstone {
+        position: Position3::new(next(), next(), next()),
+        velocity: Position3::new(next(), next(), next()),
+    }
+    .as_integer()?;
+
+    hailstones
+        .iter()
+        .all(|hailstone| {
+            hailstone
+                .as_integer()
+                .is_some_and(|hailstone| hailstone.hit_time(&rock).is_some())
+        })
+        .then_some(rock)
+}
+
+struct Solution;
+
+impl Puzzle for Solution {
+    /// The hailstones in the number types the parts are solved in.
+    type Parsed<'a> = (Vec<Hailstone<Ratio<i128>>>, Vec<Hailstone<BigRational>>);
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        (parse(input), parse(input))
+    }
+
+    fn part_one((hailstones, _): &Self::Parsed<'_>) -> Option<usize> {
+        Some(count_intersections(
+            hailstones,
+            200000000000000,
+            400000000000000,
+        ))
+    }
+
+    fn part_two((_, hailstones): &Self::Parsed<'_>) -> Option<usize> {
+        let Position3 { x, y, z } = throw_rock(hailstones)?.position;
+        (x + y + z).try_into().ok()
     }
 }
 
@@ -141,4 +258,33 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(47));
     }
+
+    #[test]
+    fn test_example_area() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(count_intersections(&parse::<f64>(&input), 7, 27), 2);
+        assert_eq!(count_intersections(&parse::<Ratio<i128>>(&input), 7, 27), 2);
+        assert_eq!(count_intersections(&parse::<BigRational>(&input), 7, 27), 2);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(part_one(""), Some(0));
+        assert_eq!(part_two(""), None);
+    }
+
+    #[test]
+    fn test_rock_hits_every_hailstone() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let rock = throw_rock(&parse::<BigRational>(&input)).unwrap();
+        assert_eq!(rock.position, Position3::new(24, 13, 10));
+        assert_eq!(rock.velocity, Position3::new(-3, 1, 2));
+
+        let hit_times: Vec<_> = parse::<i128>(&input)
+            .iter()
+            .map(|hailstone| hailstone.hit_time(&rock))
+            .collect();
+        assert_eq!(hit_times, vec![Some(5), Some(3), Some(4), Some(6), Some(1)]);
+        assert!(throw_rock(&parse::<f64>(&input)).is_some());
+    }
 }
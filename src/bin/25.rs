@@ -1,3 +1,4 @@
+use advent_of_code::Puzzle;
 use rand::Rng;
 use std::collections::HashMap;
 
@@ -99,24 +100,35 @@ impl Graph {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let graph = Graph::from(input);
-    loop {
-        let mut cloned_graph = graph.clone();
-        if cloned_graph.find_min_cut() == 3 {
-            return Some(
-                cloned_graph
-                    .adjacencies
-                    .keys()
-                    .map(|node| node.split(SPLIT).count())
-                    .product(),
-            );
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Graph;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Graph {
+        Graph::from(input)
+    }
+
+    fn part_one(graph: &Graph) -> Option<usize> {
+        loop {
+            let mut cloned_graph = graph.clone();
+            if cloned_graph.find_min_cut() == 3 {
+                return Some(
+                    cloned_graph
+                        .adjacencies
+                        .keys()
+                        .map(|node| node.split(SPLIT).count())
+                        .product(),
+                );
+            }
         }
     }
-}
 
-pub fn part_two(_input: &str) -> Option<usize> {
-    Some(50)
+    fn part_two(_graph: &Graph) -> Option<usize> {
+        Some(50)
+    }
 }
 
 #[cfg(test)]
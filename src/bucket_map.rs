@@ -0,0 +1,205 @@
+use std::{
+    collections::hash_map::RandomState,
+    fmt::{Display, Formatter, Result},
+    hash::{BuildHasher, Hash},
+};
+
+/// A key and its value, `slot` places behind the first entry of `bucket`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BucketEntry<'a, K, V> {
+    pub bucket: usize,
+    pub slot: usize,
+    pub key: &'a K,
+    pub value: &'a V,
+}
+
+/// A hash map with a fixed number of buckets, each keeping its entries in
+/// the order they were first inserted.
+#[derive(Clone, Debug)]
+pub struct BucketMap<K, V, S = RandomState> {
+    buckets: Vec<Vec<(K, V)>>,
+    hasher: S,
+}
+
+impl<K, V> BucketMap<K, V> {
+    pub fn new(buckets: usize) -> Self {
+        Self::with_hasher(buckets, RandomState::new())
+    }
+}
+
+impl<K, V, S> BucketMap<K, V, S> {
+    pub fn with_hasher(buckets: usize, hasher: S) -> Self {
+        assert!(buckets > 0, "A bucket map needs at least one bucket.");
+        Self {
+            buckets: (0..buckets).map(|_| vec![]).collect(),
+            hasher,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.buckets.iter().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buckets.iter().all(Vec::is_empty)
+    }
+
+    /// Every entry, bucket by bucket and in insertion order within a bucket.
+    pub fn iter(&self) -> impl Iterator<Item = BucketEntry<'_, K, V>> {
+        self.buckets
+            .iter()
+            .enumerate()
+            .flat_map(|(bucket, entries)| {
+                entries
+                    .iter()
+                    .enumerate()
+                    .map(move |(slot, (key, value))| BucketEntry {
+                        bucket,
+                        slot,
+                        key,
+                        value,
+                    })
+            })
+    }
+
+    /// The sum of `weight` over every entry, scaled by its one-based bucket
+    /// and slot numbers.
+    pub fn weighted_sum<F>(&self, mut weight: F) -> usize
+    where
+        F: FnMut(&K, &V) -> usize,
+    {
+        self.iter()
+            .map(|entry| (entry.bucket + 1) * (entry.slot + 1) * weight(entry.key, entry.value))
+            .sum()
+    }
+}
+
+impl<K, V, S> BucketMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    pub fn bucket_of(&self, key: &K) -> usize {
+        (self.hasher.hash_one(key) % self.buckets.len() as u64) as usize
+    }
+
+    fn find(&self, key: &K) -> (usize, Option<usize>) {
+        let bucket = self.bucket_of(key);
+        let slot = self.buckets[bucket]
+            .iter()
+            .position(|(other, _)| other == key);
+        (bucket, slot)
+    }
+
+    /// Replaces the value of `key` in place if present, returning the old
+    /// one, and appends it to the end of its bucket otherwise.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.find(&key) {
+            (bucket, Some(slot)) => {
+                Some(std::mem::replace(&mut self.buckets[bucket][slot].1, value))
+            }
+            (bucket, None) => {
+                self.buckets[bucket].push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Removes `key`, moving the entries behind it in its bucket forward.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (bucket, slot) = self.find(key);
+        slot.map(|slot| self.buckets[bucket].remove(slot).1)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let (bucket, slot) = self.find(key);
+        slot.map(|slot| &self.buckets[bucket][slot].1)
+    }
+}
+
+/// Lists the non-empty buckets, one per line, as `Box 3: [pc 4] [ot 9]`.
+impl<K, V, S> Display for BucketMap<K, V, S>
+where
+    K: Display,
+    V: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for (bucket, entries) in self.buckets.iter().enumerate() {
+            if entries.is_empty() {
+                continue;
+            }
+            write!(f, "Box {}:", bucket)?;
+            for (key, value) in entries {
+                write!(f, " [{} {}]", key, value)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BucketMap;
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    /// Hashes to the last byte written, so tests can pick the buckets.
+    #[derive(Default)]
+    struct LastByte(u64);
+
+    impl Hasher for LastByte {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            if let Some(&byte) = bytes.last() {
+                self.0 = byte as u64;
+            }
+        }
+    }
+
+    #[test]
+    fn ordered_buckets() {
+        let mut map = BucketMap::with_hasher(4, BuildHasherDefault::<LastByte>::default());
+        assert!(map.is_empty());
+        assert_eq!(map.insert(1u8, 'a'), None);
+        assert_eq!(map.insert(5u8, 'b'), None);
+        assert_eq!(map.insert(2u8, 'c'), None);
+        assert_eq!(map.insert(1u8, 'd'), Some('a'));
+        assert_eq!(map.bucket_of(&5), 1);
+
+        let entries: Vec<(usize, usize, u8, char)> = map
+            .iter()
+            .map(|entry| (entry.bucket, entry.slot, *entry.key, *entry.value))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![(1, 0, 1, 'd'), (1, 1, 5, 'b'), (2, 0, 2, 'c')]
+        );
+        assert_eq!(
+            map.weighted_sum(|&key, _| key as usize),
+            2 + 2 * 2 * 5 + 3 * 2
+        );
+        assert_eq!(map.to_string(), "Box 1: [1 d] [5 b]\nBox 2: [2 c]\n");
+
+        assert_eq!(map.remove(&1), Some('d'));
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.get(&5), Some(&'b'));
+        assert_eq!(map.iter().next().map(|entry| entry.slot), Some(0));
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn random_state() {
+        let mut map = BucketMap::new(16);
+        (0..100).for_each(|key| {
+            map.insert(key, key * key);
+        });
+        assert_eq!(map.len(), 100);
+        assert_eq!(map.get(&9), Some(&81));
+        assert!(map
+            .iter()
+            .all(|entry| entry.bucket == map.bucket_of(entry.key)));
+    }
+}
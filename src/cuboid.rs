@@ -0,0 +1,144 @@
+use std::ops::{Add, Sub};
+
+use num::{range_inclusive, PrimInt, Zero};
+
+use crate::{Axis, Position3};
+
+/// An axis-aligned box spanning `min..=max` on every axis.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub struct Cuboid<T> {
+    pub min: Position3<T>,
+    pub max: Position3<T>,
+}
+
+impl<T> Cuboid<T>
+where
+    T: Ord + Copy,
+{
+    /// Creates the box between two opposite corners, given in any order.
+    pub fn new(a: Position3<T>, b: Position3<T>) -> Self {
+        Self {
+            min: Position3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Position3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    pub fn contains(&self, position: &Position3<T>) -> bool {
+        self.min.x <= position.x
+            && position.x <= self.max.x
+            && self.min.y <= position.y
+            && position.y <= self.max.y
+            && self.min.z <= position.z
+            && position.z <= self.max.z
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Position3::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Position3::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Self { min, max })
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Flattens the box onto the plane through the origin orthogonal to
+    /// `axis`, e.g. `Axis::Z` gives its footprint in the xy plane.
+    pub fn projection(&self, axis: Axis) -> Self
+    where
+        T: Zero,
+    {
+        Self {
+            min: self.min.with(axis, T::zero()),
+            max: self.max.with(axis, T::zero()),
+        }
+    }
+
+    /// Moves the box along `axis` so that its lower bound becomes `value`.
+    pub fn with_min(&self, axis: Axis, value: T) -> Self
+    where
+        T: Add<Output = T> + Sub<Output = T>,
+    {
+        let extent = self.max.get(axis) - self.min.get(axis);
+        Self {
+            min: self.min.with(axis, value),
+            max: self.max.with(axis, value + extent),
+        }
+    }
+}
+
+impl<T> Cuboid<T>
+where
+    T: Add<Output = T> + Copy,
+{
+    pub fn translate(&self, offset: Position3<T>) -> Self {
+        Self {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+}
+
+impl<T> Cuboid<T>
+where
+    T: PrimInt,
+{
+    /// All unit cells inside the box.
+    pub fn positions(&self) -> impl Iterator<Item = Position3<T>> {
+        let Self { min, max } = *self;
+        range_inclusive(min.x, max.x).flat_map(move |x| {
+            range_inclusive(min.y, max.y).flat_map(move |y| {
+                range_inclusive(min.z, max.z).map(move |z| Position3::new(x, y, z))
+            })
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Cuboid;
+    use crate::{Axis, Position3};
+
+    fn cuboid(a: (i32, i32, i32), b: (i32, i32, i32)) -> Cuboid<i32> {
+        Cuboid::new(a.into(), b.into())
+    }
+
+    #[test]
+    fn intersection() {
+        let a = cuboid((0, 0, 0), (2, 2, 2));
+        let b = cuboid((3, 1, 1), (1, 5, 5));
+
+        assert_eq!(a.intersection(&b), Some(cuboid((1, 1, 1), (2, 2, 2))));
+        assert!(!a.intersects(&cuboid((3, 0, 0), (4, 2, 2))));
+    }
+
+    #[test]
+    fn projection() {
+        let a = cuboid((1, 0, 1), (1, 2, 1));
+        let b = cuboid((0, 0, 2), (2, 0, 2));
+
+        assert!(!a.intersects(&b));
+        assert!(a.projection(Axis::Z).intersects(&b.projection(Axis::Z)));
+    }
+
+    #[test]
+    fn translation() {
+        let a = cuboid((0, 0, 5), (1, 0, 7));
+
+        assert_eq!(
+            a.translate(Position3::new(1, 2, -4)),
+            cuboid((1, 2, 1), (2, 2, 3))
+        );
+        assert_eq!(a.with_min(Axis::Z, 1), cuboid((0, 0, 1), (1, 0, 3)));
+        assert_eq!(a.positions().count(), 6);
+    }
+}
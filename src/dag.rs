@@ -0,0 +1,78 @@
+use std::{collections::VecDeque, ops::AddAssign};
+
+/// An order of the nodes `0..len` in which every edge points forward, or
+/// `None` if the edges form a cycle.
+pub fn topological_order<F, I>(len: usize, mut successors: F) -> Option<Vec<usize>>
+where
+    F: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    let mut in_degrees = vec![0; len];
+    (0..len).for_each(|node| {
+        successors(node)
+            .into_iter()
+            .for_each(|next| in_degrees[next] += 1)
+    });
+
+    let mut ready: VecDeque<usize> = (0..len).filter(|&node| in_degrees[node] == 0).collect();
+    let mut order = Vec::with_capacity(len);
+    while let Some(node) = ready.pop_front() {
+        order.push(node);
+        successors(node).into_iter().for_each(|next| {
+            in_degrees[next] -= 1;
+            if in_degrees[next] == 0 {
+                ready.push_back(next);
+            }
+        });
+    }
+    (order.len() == len).then_some(order)
+}
+
+/// Adds the final count of every node to each of its successors, so a node
+/// ends up with its initial count plus the final counts of the nodes with an
+/// edge into it. `None` if the edges form a cycle.
+pub fn propagate_forward<T, F, I>(initial: Vec<T>, mut successors: F) -> Option<Vec<T>>
+where
+    T: AddAssign + Clone,
+    F: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    let order = topological_order(initial.len(), &mut successors)?;
+    let mut counts = initial;
+    order.into_iter().for_each(|node| {
+        let count = counts[node].clone();
+        successors(node)
+            .into_iter()
+            .for_each(|next| counts[next] += count.clone());
+    });
+    Some(counts)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{propagate_forward, topological_order};
+
+    #[test]
+    fn order() {
+        let edges = [vec![], vec![0, 3], vec![1], vec![0]];
+        assert_eq!(
+            topological_order(4, |node| edges[node].clone()),
+            Some(vec![2, 1, 3, 0])
+        );
+
+        let cycle = [vec![1], vec![2], vec![0]];
+        assert_eq!(topological_order(3, |node| cycle[node].clone()), None);
+    }
+
+    #[test]
+    fn paths() {
+        // the number of paths from node 0 in a diamond with a tail
+        let edges = [vec![1, 2], vec![3], vec![3], vec![4], vec![]];
+        let mut initial = vec![0u64; 5];
+        initial[0] = 1;
+        assert_eq!(
+            propagate_forward(initial, |node| edges[node].iter().copied()),
+            Some(vec![1, 1, 1, 2, 2])
+        );
+    }
+}
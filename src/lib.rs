@@ -1,18 +1,55 @@
+mod aho_corasick;
+mod ascii;
+mod bucket_map;
+mod cuboid;
+mod dag;
 mod day;
 pub mod dijkstra;
 mod direction;
+mod linear_system;
 mod matrix;
+mod memo;
 mod movable;
 mod picks_theorem;
+mod polygon;
+mod polynomial;
 mod position;
+mod position3;
+mod prefix_sum;
+mod priority_queue;
+mod puzzle;
+mod quadratic;
+mod reflection;
+mod region;
 mod shoelace_formula;
+mod span;
 pub mod template;
+pub mod visualize;
+mod voxel_grid;
 
+pub use aho_corasick::*;
+pub use ascii::*;
+pub use bucket_map::*;
+pub use cuboid::*;
+pub use dag::*;
 pub use day::*;
 pub use dijkstra::dijkstra_search;
 pub use direction::*;
+pub use linear_system::*;
 pub use matrix::*;
+pub use memo::*;
 pub use movable::*;
 pub use picks_theorem::*;
+pub use polygon::*;
+pub use polynomial::*;
 pub use position::*;
+pub use position3::*;
+pub use prefix_sum::*;
+pub use priority_queue::*;
+pub use puzzle::*;
+pub use quadratic::*;
+pub use reflection::*;
+pub use region::*;
 pub use shoelace_formula::*;
+pub use span::*;
+pub use voxel_grid::*;
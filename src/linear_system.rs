@@ -0,0 +1,53 @@
+use std::cmp::Ordering;
+
+use num::{Num, Signed};
+
+/// Solves `coefficients · x = constants` by Gaussian elimination with partial
+/// pivoting, returning `None` if the system has no unique solution.
+///
+/// The arithmetic is whatever `T` provides, so `f64` gives an approximation
+/// and `num::BigRational` an exact result.
+pub fn solve_linear_system<T>(
+    mut coefficients: Vec<Vec<T>>,
+    mut constants: Vec<T>,
+) -> Option<Vec<T>>
+where
+    T: Num + Signed + PartialOrd + Clone,
+{
+    let n = constants.len();
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&a, &b| {
+            coefficients[a][col]
+                .abs()
+                .partial_cmp(&coefficients[b][col].abs())
+                .unwrap_or(Ordering::Equal)
+        })?;
+        if coefficients[pivot][col].is_zero() {
+            return None;
+        }
+        coefficients.swap(col, pivot);
+        constants.swap(col, pivot);
+
+        let (pivot_rows, rows) = coefficients.split_at_mut(col + 1);
+        let pivot_row = &pivot_rows[col];
+        for (row, coefficients) in (col + 1..n).zip(rows) {
+            let factor = coefficients[col].clone() / pivot_row[col].clone();
+            for (cell, pivot_cell) in coefficients.iter_mut().zip(pivot_row).skip(col) {
+                *cell = cell.clone() - pivot_cell.clone() * factor.clone();
+            }
+            let eliminated = constants[col].clone() * factor;
+            constants[row] = constants[row].clone() - eliminated;
+        }
+    }
+
+    let mut solution = vec![T::zero(); n];
+    for row in (0..n).rev() {
+        let known = (row + 1..n).fold(T::zero(), |sum, col| {
+            sum + coefficients[row][col].clone() * solution[col].clone()
+        });
+        solution[row] = (constants[row].clone() - known) / coefficients[row][row].clone();
+    }
+
+    Some(solution)
+}
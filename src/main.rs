@@ -1,4 +1,4 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{all, download, examples, read, scaffold, solve};
 use args::{parse, AppArguments};
 
 mod args {
@@ -10,11 +10,20 @@ mod args {
         Download {
             day: Day,
         },
+        Examples {
+            day: Day,
+            force: bool,
+        },
         Read {
             day: Day,
         },
         Scaffold {
             day: Day,
+            template: Option<String>,
+            force: bool,
+            update_tests: bool,
+            part_one: Option<String>,
+            part_two: Option<String>,
         },
         Solve {
             day: Day,
@@ -39,11 +48,20 @@ mod args {
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
+            Some("examples") => AppArguments::Examples {
+                day: args.free_from_str()?,
+                force: args.contains("--force"),
+            },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
+                template: args.opt_value_from_str("--template")?,
+                force: args.contains("--force"),
+                update_tests: args.contains("--update-tests"),
+                part_one: args.opt_value_from_str("--part-one")?,
+                part_two: args.opt_value_from_str("--part-two")?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
@@ -79,8 +97,35 @@ fn main() {
         Ok(args) => match args {
             AppArguments::All { release, time } => all::handle(release, time),
             AppArguments::Download { day } => download::handle(day),
+            AppArguments::Examples { day, force } => examples::handle(day, force),
             AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::Scaffold {
+                day,
+                template,
+                force,
+                update_tests,
+                part_one,
+                part_two,
+            } => {
+                let existing = match (force, update_tests) {
+                    (false, false) => scaffold::Existing::Refuse,
+                    (true, false) => scaffold::Existing::Overwrite,
+                    (false, true) => scaffold::Existing::UpdateTests,
+                    (true, true) => {
+                        eprintln!("Error: --force and --update-tests cannot be combined.");
+                        std::process::exit(1);
+                    }
+                };
+                scaffold::handle(
+                    day,
+                    scaffold::Options {
+                        template: template.unwrap_or_else(|| scaffold::DEFAULT_TEMPLATE.into()),
+                        existing,
+                        part_one,
+                        part_two,
+                    },
+                );
+            }
             AppArguments::Solve {
                 day,
                 release,
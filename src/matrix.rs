@@ -5,7 +5,7 @@ use std::{
 
 use crate::Position;
 
-#[derive(PartialEq, Eq, Clone, Hash)]
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
 pub struct Matrix<T> {
     cells: Vec<Vec<T>>,
     pub rows: usize,
@@ -102,6 +102,23 @@ impl<T> Matrix<T> {
             .into()
     }
 
+    pub fn map_with_position<U, F>(&self, mut f: F) -> Matrix<U>
+    where
+        F: FnMut(Idx, &T) -> U,
+    {
+        self.row_iter()
+            .enumerate()
+            .map(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .map(|(col, cell)| f((row + 1, col + 1).into(), cell))
+                    .collect()
+            })
+            .collect::<Vec<Vec<U>>>()
+            .into()
+    }
+
     pub fn adjacent_positions(&self, position: &Idx) -> Vec<Idx> {
         position
             .adjacent_positions()
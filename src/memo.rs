@@ -0,0 +1,92 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Cache for recursive functions whose arguments can be described by a small
+/// key, e.g. offsets into borrowed slices instead of owned copies of them.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluates `f` for `key`, reusing cached results.
+    ///
+    /// `f` receives a `recurse` callback for its sub-problems, so the
+    /// recursion itself can be written as a pure function.
+    pub fn get<F>(&mut self, key: K, f: &F) -> V
+    where
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+
+        let value = f(&mut |key| self.get(key, f), key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/// Evaluates the recursive function `f` for `key` with a fresh [`Memo`].
+pub fn memoize<K, V, F>(key: K, f: F) -> V
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    Memo::new().get(key, &f)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{memoize, Memo};
+
+    fn fibonacci(recurse: &mut dyn FnMut(u64) -> u64, n: u64) -> u64 {
+        match n {
+            0 | 1 => n,
+            _ => recurse(n - 1) + recurse(n - 2),
+        }
+    }
+
+    #[test]
+    fn fibonacci_cache() {
+        let mut memo = Memo::new();
+        assert!(memo.is_empty());
+        assert_eq!(memo.get(90, &fibonacci), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 91);
+
+        assert_eq!(memo.get(50, &fibonacci), 12_586_269_025);
+        assert_eq!(memo.len(), 91);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memoize(10, fibonacci), 55);
+    }
+}
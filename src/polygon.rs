@@ -0,0 +1,130 @@
+use crate::{Direction, Movable, Position};
+
+type Idx = Position<isize>;
+
+/// A rectilinear loop traced one `(Direction, length)` command at a time.
+///
+/// Only the corners are stored; the perimeter and the shoelace sum are
+/// updated as the walker moves, so long edges cost no more than short ones.
+#[derive(Clone, Debug)]
+pub struct PolygonBuilder {
+    walker: Movable<isize>,
+    vertices: Vec<Idx>,
+    perimeter: usize,
+    /// Twice the signed area swept so far, relative to the start.
+    twice_area: isize,
+}
+
+impl PolygonBuilder {
+    pub fn new(start: Idx) -> Self {
+        Self {
+            walker: Movable::new(start, Direction::Right),
+            vertices: vec![start],
+            perimeter: 0,
+            twice_area: 0,
+        }
+    }
+
+    pub fn push(&mut self, direction: Direction, length: usize) -> &mut Self {
+        let from = self.walker.position;
+        let continues = self.vertices.len() > 1 && self.walker.direction == direction;
+        let to = self
+            .walker
+            .turn_to(&direction)
+            .move_forward(length as isize)
+            .position;
+
+        self.perimeter += length;
+        self.twice_area += from.col * to.row - to.col * from.row;
+        if continues {
+            *self.vertices.last_mut().unwrap() = to;
+        } else {
+            self.vertices.push(to);
+        }
+        self
+    }
+
+    /// The start followed by every corner the walker turned at, and where it is now.
+    pub fn vertices(&self) -> &[Idx] {
+        &self.vertices
+    }
+
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.walker.position == self.vertices[0]
+    }
+
+    /// The area enclosed by the loop through the cell centers.
+    pub fn area(&self) -> usize {
+        debug_assert!(self.is_closed(), "The polygon must be closed.");
+        self.twice_area.unsigned_abs() / 2
+    }
+
+    /// The number of cells strictly inside the loop, by Pick's theorem.
+    pub fn interior_points(&self) -> usize {
+        self.area() + 1 - self.perimeter / 2
+    }
+
+    /// The number of cells inside the loop or on it.
+    pub fn area_with_boundary(&self) -> usize {
+        self.interior_points() + self.perimeter
+    }
+}
+
+impl Extend<(Direction, usize)> for PolygonBuilder {
+    fn extend<I: IntoIterator<Item = (Direction, usize)>>(&mut self, commands: I) {
+        commands.into_iter().for_each(|(direction, length)| {
+            self.push(direction, length);
+        });
+    }
+}
+
+impl FromIterator<(Direction, usize)> for PolygonBuilder {
+    /// Traces the commands starting at the origin.
+    fn from_iter<I: IntoIterator<Item = (Direction, usize)>>(commands: I) -> Self {
+        let mut polygon = Self::new((0, 0).into());
+        polygon.extend(commands);
+        polygon
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PolygonBuilder;
+    use crate::Direction::*;
+
+    #[test]
+    fn square() {
+        let polygon: PolygonBuilder = [(Right, 2), (Right, 1), (Down, 3), (Left, 3), (Up, 3)]
+            .into_iter()
+            .collect();
+
+        assert!(polygon.is_closed());
+        assert_eq!(
+            polygon.vertices(),
+            &[
+                (0, 0).into(),
+                (0, 3).into(),
+                (3, 3).into(),
+                (3, 0).into(),
+                (0, 0).into()
+            ]
+        );
+        assert_eq!(polygon.perimeter(), 12);
+        assert_eq!(polygon.area(), 9);
+        assert_eq!(polygon.interior_points(), 4);
+        assert_eq!(polygon.area_with_boundary(), 16);
+    }
+
+    #[test]
+    fn counter_clockwise() {
+        let mut polygon = PolygonBuilder::new((5, 5).into());
+        polygon.extend([(Up, 1_000_000), (Left, 1_000_000), (Down, 1_000_000)]);
+        assert!(!polygon.is_closed());
+        polygon.push(Right, 1_000_000);
+        assert_eq!(polygon.area_with_boundary(), 1_000_001 * 1_000_001);
+    }
+}
@@ -0,0 +1,157 @@
+use num::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, One, Zero};
+
+/// Numbers the interpolation works in, exactly and with every operation
+/// checked, such as primitive integers or `Ratio`s. Every function returns
+/// `None` on overflow, or when an integer result would need rounding.
+pub trait Exact:
+    Clone + PartialEq + Zero + One + FromPrimitive + CheckedAdd + CheckedSub + CheckedMul + CheckedDiv
+{
+}
+
+impl<T> Exact for T where
+    T: Clone
+        + PartialEq
+        + Zero
+        + One
+        + FromPrimitive
+        + CheckedAdd
+        + CheckedSub
+        + CheckedMul
+        + CheckedDiv
+{
+}
+
+/// The forward differences of `values`: the first row holds the values, and
+/// every further row the differences of the row before, until one is all zero.
+pub fn difference_table<T: Exact>(values: &[T]) -> Option<Vec<Vec<T>>> {
+    let mut table = vec![values.to_vec()];
+    loop {
+        let row = table.last().unwrap();
+        if row.len() <= 1 || row.iter().all(Zero::is_zero) {
+            return Some(table);
+        }
+        let differences = row
+            .windows(2)
+            .map(|pair| pair[1].checked_sub(&pair[0]))
+            .collect::<Option<Vec<T>>>()?;
+        table.push(differences);
+    }
+}
+
+/// The generalized binomial coefficient `x·(x-1)···(x-k+1) / k!`.
+fn binomial<T: Exact>(x: &T, k: usize) -> Option<T> {
+    (0..k).try_fold(T::one(), |binomial, i| {
+        let factor = x.checked_sub(&T::from_usize(i)?)?;
+        let divisor = T::from_usize(i + 1)?;
+        exact_div(&binomial.checked_mul(&factor)?, &divisor)
+    })
+}
+
+fn exact_div<T: Exact>(a: &T, b: &T) -> Option<T> {
+    let quotient = a.checked_div(b)?;
+    (quotient.checked_mul(b)? == *a).then_some(quotient)
+}
+
+/// The value at `x` of the polynomial through `(i, values[i])`, built from
+/// the differences at the first value.
+pub fn newton_forward<T: Exact>(values: &[T], x: T) -> Option<T> {
+    difference_table(values)?
+        .iter()
+        .enumerate()
+        .try_fold(T::zero(), |sum, (k, row)| {
+            sum.checked_add(&row.first()?.checked_mul(&binomial(&x, k)?)?)
+        })
+}
+
+/// The value at `x` of the polynomial through `(i, values[i])`, built from
+/// the differences at the last value, which suits extrapolating forwards.
+pub fn newton_backward<T: Exact>(values: &[T], x: T) -> Option<T> {
+    let steps = x.checked_sub(&T::from_usize(values.len().checked_sub(1)?)?)?;
+    difference_table(values)?
+        .iter()
+        .enumerate()
+        .try_fold(T::zero(), |sum, (k, row)| {
+            let offset = steps
+                .checked_add(&T::from_usize(k)?)?
+                .checked_sub(&T::one())?;
+            sum.checked_add(&row.last()?.checked_mul(&binomial(&offset, k)?)?)
+        })
+}
+
+/// The value at `x` of the polynomial through `points`, whose x are distinct
+/// but need not be evenly spaced.
+pub fn lagrange<T: Exact>(points: &[(T, T)], x: T) -> Option<T> {
+    // sum up y_i · Π (x - x_j) / Π (x_i - x_j) as a single fraction
+    let (numerator, denominator) = points.iter().enumerate().try_fold(
+        (T::zero(), T::one()),
+        |(numerator, denominator), (i, (xi, yi))| {
+            let (mut term, mut divisor) = (yi.clone(), T::one());
+            for (j, (xj, _)) in points.iter().enumerate() {
+                if i != j {
+                    term = term.checked_mul(&x.checked_sub(xj)?)?;
+                    divisor = divisor.checked_mul(&xi.checked_sub(xj)?)?;
+                }
+            }
+            let numerator = numerator
+                .checked_mul(&divisor)?
+                .checked_add(&term.checked_mul(&denominator)?)?;
+            Some((numerator, denominator.checked_mul(&divisor)?))
+        },
+    )?;
+    exact_div(&numerator, &denominator)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{difference_table, lagrange, newton_backward, newton_forward};
+    use num::rational::Ratio;
+
+    #[test]
+    fn differences() {
+        let table = difference_table(&[1, 3, 6, 10, 15, 21]).unwrap();
+        assert_eq!(
+            table,
+            vec![
+                vec![1, 3, 6, 10, 15, 21],
+                vec![2, 3, 4, 5, 6],
+                vec![1, 1, 1, 1],
+                vec![0, 0, 0],
+            ]
+        );
+        assert_eq!(difference_table(&[i8::MIN, i8::MAX]), None);
+    }
+
+    #[test]
+    fn newton() {
+        let values = [10, 13, 16, 21, 30, 45];
+        assert_eq!(newton_forward(&values, 6), Some(68));
+        assert_eq!(newton_backward(&values, 6), Some(68));
+        assert_eq!(newton_forward(&values, -1), Some(5));
+        assert_eq!(newton_backward(&values, -1), Some(5));
+        assert_eq!(newton_forward(&values, 3), Some(21));
+
+        let squares = [0i64, 1, 4];
+        assert_eq!(newton_forward(&squares, 4_000_000_000), None);
+        assert_eq!(
+            newton_forward(&[0i128, 1, 4], 4_000_000_000),
+            Some(16 * 10i128.pow(18))
+        );
+    }
+
+    #[test]
+    fn lagrange_interpolation() {
+        let points = [(65, 3)];
+        assert_eq!(lagrange(&points, 1000), Some(3));
+
+        // y = x² / 2 is only integral at even x
+        let points = [(0, 0), (2, 2), (4, 8)];
+        assert_eq!(lagrange(&points, 6), Some(18));
+        assert_eq!(lagrange(&points, 3), None);
+
+        let points = points.map(|(x, y)| (Ratio::from_integer(x), Ratio::from_integer(y)));
+        assert_eq!(
+            lagrange(&points, Ratio::from_integer(3)),
+            Some(Ratio::new(9, 2))
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub struct Position3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> From<(T, T, T)> for Position3<T> {
+    fn from(value: (T, T, T)) -> Self {
+        Self::new(value.0, value.1, value.2)
+    }
+}
+
+impl<T> Position3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> Position3<T>
+where
+    T: Copy,
+{
+    pub fn get(&self, axis: Axis) -> T {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => self.z,
+        }
+    }
+
+    pub fn with(mut self, axis: Axis, value: T) -> Self {
+        match axis {
+            Axis::X => self.x = value,
+            Axis::Y => self.y = value,
+            Axis::Z => self.z = value,
+        }
+        self
+    }
+}
+
+impl<T> Add for Position3<T>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T> Sub for Position3<T>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T> Mul<T> for Position3<T>
+where
+    T: Mul<Output = T> + Clone,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs.clone(), self.y * rhs.clone(), self.z * rhs)
+    }
+}
+
+impl<T> Position3<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Clone,
+{
+    pub fn dot(&self, other: &Self) -> T {
+        self.x.clone() * other.x.clone()
+            + self.y.clone() * other.y.clone()
+            + self.z.clone() * other.z.clone()
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        let Self { x, y, z } = self.clone();
+        let Self {
+            x: other_x,
+            y: other_y,
+            z: other_z,
+        } = other.clone();
+        Self::new(
+            y.clone() * other_z.clone() - z.clone() * other_y.clone(),
+            z * other_x.clone() - x.clone() * other_z,
+            x * other_y - y * other_x,
+        )
+    }
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
@@ -0,0 +1,116 @@
+use std::ops::{Add, Range, Sub};
+
+use num::Zero;
+
+use crate::{Matrix, Position};
+
+/// Cumulative sums of a sequence, answering range sums in constant time.
+#[derive(Clone, Debug)]
+pub struct PrefixSum<T> {
+    // sums[i] is the sum of the first `i` values
+    sums: Vec<T>,
+}
+
+impl<T> FromIterator<T> for PrefixSum<T>
+where
+    T: Add<Output = T> + Zero + Copy,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut sums = vec![T::zero()];
+        let mut sum = T::zero();
+        for value in iter {
+            sum = sum + value;
+            sums.push(sum);
+        }
+        Self { sums }
+    }
+}
+
+impl<T> PrefixSum<T>
+where
+    T: Sub<Output = T> + Copy,
+{
+    pub fn len(&self) -> usize {
+        self.sums.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The sum of the first `count` values.
+    pub fn prefix(&self, count: usize) -> T {
+        self.sums[count]
+    }
+
+    pub fn sum(&self, range: Range<usize>) -> T {
+        self.sums[range.end] - self.sums[range.start]
+    }
+
+    pub fn total(&self) -> T {
+        self.sums[self.len()]
+    }
+}
+
+/// Cumulative sums of a [`Matrix`], answering sums over rectangles in constant time.
+#[derive(Clone, Debug)]
+pub struct PrefixSum2<T> {
+    // sums[row][col] is the sum of the `row` x `col` top left cells
+    sums: Vec<Vec<T>>,
+}
+
+impl<T> From<&Matrix<T>> for PrefixSum2<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Zero + Copy,
+{
+    fn from(matrix: &Matrix<T>) -> Self {
+        let mut sums = vec![vec![T::zero(); matrix.cols + 1]; matrix.rows + 1];
+        for (row, cells) in matrix.row_iter().enumerate() {
+            for (col, &cell) in cells.iter().enumerate() {
+                sums[row + 1][col + 1] =
+                    cell + sums[row][col + 1] + sums[row + 1][col] - sums[row][col];
+            }
+        }
+        Self { sums }
+    }
+}
+
+impl<T> PrefixSum2<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    /// The sum of the cells between two corners, both inclusive and indexed like [`Matrix`].
+    pub fn sum(&self, top_left: Position<usize>, bottom_right: Position<usize>) -> T {
+        let (top, left) = (top_left.row - 1, top_left.col - 1);
+        let (bottom, right) = (bottom_right.row, bottom_right.col);
+        self.sums[bottom][right] + self.sums[top][left]
+            - self.sums[top][right]
+            - self.sums[bottom][left]
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PrefixSum, PrefixSum2};
+    use crate::Matrix;
+
+    #[test]
+    fn range_sums() {
+        let sums: PrefixSum<i32> = [3, -1, 4, 1, 5].into_iter().collect();
+
+        assert_eq!(sums.len(), 5);
+        assert_eq!(sums.prefix(2), 2);
+        assert_eq!(sums.sum(1..4), 4);
+        assert_eq!(sums.total(), 12);
+    }
+
+    #[test]
+    fn rectangle_sums() {
+        let matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+        let sums = PrefixSum2::from(&matrix);
+
+        assert_eq!(sums.sum((1, 1).into(), (3, 3).into()), 45);
+        assert_eq!(sums.sum((2, 2).into(), (3, 3).into()), 28);
+        assert_eq!(sums.sum((1, 3).into(), (2, 3).into()), 9);
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::BinaryHeap;
+
+/// Stable counterparts of the nightly-only `BinaryHeap::drain_sorted` and
+/// `BinaryHeap::into_iter_sorted`.
+pub trait SortedDrain<T> {
+    /// Removes the elements from greatest to least; the ones not yet taken
+    /// stay in the heap.
+    fn drain_ordered(&mut self) -> DrainOrdered<'_, T>;
+
+    /// Yields the elements from greatest to least.
+    fn into_ordered_iter(self) -> IntoOrderedIter<T>;
+}
+
+pub struct DrainOrdered<'a, T>(&'a mut BinaryHeap<T>);
+
+pub struct IntoOrderedIter<T>(BinaryHeap<T>);
+
+impl<T: Ord> SortedDrain<T> for BinaryHeap<T> {
+    fn drain_ordered(&mut self) -> DrainOrdered<'_, T> {
+        DrainOrdered(self)
+    }
+
+    fn into_ordered_iter(self) -> IntoOrderedIter<T> {
+        IntoOrderedIter(self)
+    }
+}
+
+impl<T: Ord> Iterator for DrainOrdered<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T: Ord> Iterator for IntoOrderedIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::SortedDrain;
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    #[test]
+    fn ordered() {
+        let heap = BinaryHeap::from([3, 1, 4, 1, 5, 9, 2, 6]);
+        let sorted: Vec<_> = heap.into_ordered_iter().collect();
+        assert_eq!(sorted, vec![9, 6, 5, 4, 3, 2, 1, 1]);
+
+        let mut heap: BinaryHeap<_> = [3, 1, 4].into_iter().map(Reverse).collect();
+        let smallest: Vec<_> = heap.drain_ordered().take(2).map(|Reverse(x)| x).collect();
+        assert_eq!(smallest, vec![1, 3]);
+        assert_eq!(heap.into_vec(), vec![Reverse(4)]);
+    }
+}
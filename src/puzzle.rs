@@ -0,0 +1,54 @@
+use std::{fmt::Display, io};
+
+/// A day's puzzle: the input is parsed once, and both parts are answered
+/// from the parsed value.
+///
+/// ```
+/// # use advent_of_code::Puzzle;
+/// struct Solution;
+///
+/// impl Puzzle for Solution {
+///     type Parsed<'a> = Vec<&'a str>;
+///     type PartOne = usize;
+///     type PartTwo = usize;
+///
+///     fn parse(input: &str) -> Self::Parsed<'_> {
+///         input.lines().collect()
+///     }
+///
+///     fn part_one(lines: &Self::Parsed<'_>) -> Option<usize> {
+///         Some(lines.len())
+///     }
+///
+///     fn part_two(lines: &Self::Parsed<'_>) -> Option<usize> {
+///         lines.iter().map(|line| line.len()).max()
+///     }
+/// }
+///
+/// let lines = Solution::parse("a\nbcd");
+/// assert_eq!(Solution::part_two(&lines), Some(3));
+/// ```
+pub trait Puzzle {
+    /// The parsed input, which may borrow from the input.
+    type Parsed<'a>;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Self::Parsed<'_>;
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Option<Self::PartOne>;
+
+    fn part_two(parsed: &Self::Parsed<'_>) -> Option<Self::PartTwo>;
+
+    /// A problem with the input that keeps the parts from answering, which
+    /// the runner reports once after parsing.
+    fn diagnostic(_parsed: &Self::Parsed<'_>) -> Option<String> {
+        None
+    }
+
+    /// Writes recordings and other exports. The runner calls this once,
+    /// outside the timed parts, and only when `AOC_RECORD` is set.
+    fn export(_parsed: &Self::Parsed<'_>) -> io::Result<()> {
+        Ok(())
+    }
+}
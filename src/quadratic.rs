@@ -0,0 +1,109 @@
+use num::{BigInt, Integer, Signed, ToPrimitive};
+use std::ops::RangeInclusive;
+
+/// The largest integer whose square does not exceed `n`, by Newton's method:
+/// starting above the root, the iterates decrease until they reach it.
+pub fn isqrt<T: Integer + Clone>(n: T) -> T {
+    assert!(
+        n >= T::zero(),
+        "Only non-negative numbers have a square root."
+    );
+    let two = T::one() + T::one();
+    if n < two {
+        return n;
+    }
+    // (n/2 + 1)² ≥ n, and `root + n / root` never exceeds the start
+    let mut root = n.clone() / two.clone() + T::one();
+    loop {
+        let next = (root.clone() + n.clone() / root.clone()) / two.clone();
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+/// The integers `x` for which `a·x² + b·x + c > 0`, for a downward parabola
+/// (`a < 0`), or `None` if there are none.
+///
+/// The computation is exact for all coefficients; it panics only if the
+/// range itself does not fit in `i128`.
+pub fn quadratic_positive_range(a: i128, b: i128, c: i128) -> Option<RangeInclusive<i128>> {
+    assert!(
+        a < 0,
+        "The parabola must open downwards to be positive on a range."
+    );
+    // b² alone overflows i128 once |b| passes 2^63.5, so use big integers
+    let (a, b, c) = (BigInt::from(a), BigInt::from(b), BigInt::from(c));
+    let f = |x: &BigInt| &a * x * x + &b * x + &c;
+
+    let discriminant = &b * &b - BigInt::from(4) * &a * &c;
+    if !discriminant.is_positive() {
+        return None;
+    }
+    let root = isqrt(discriminant);
+
+    // the real roots are (b ± √D) / 2|a|, rounded outwards
+    let denominator = BigInt::from(-2) * &a;
+    let mut start = (&b - &root).div_floor(&denominator);
+    let mut end = (&b + &root).div_floor(&denominator) + 1;
+    while !f(&start).is_positive() && start <= end {
+        start += 1;
+    }
+    while !f(&end).is_positive() && start <= end {
+        end -= 1;
+    }
+    while f(&(&start - 1)).is_positive() {
+        start -= 1;
+    }
+    while f(&(&end + 1)).is_positive() {
+        end += 1;
+    }
+
+    let bound = |x: BigInt| x.to_i128().expect("The range must fit in i128.");
+    (start <= end).then(|| bound(start)..=bound(end))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{isqrt, quadratic_positive_range};
+    use num::BigInt;
+
+    #[test]
+    fn integer_square_root() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt::<u128>((1 << 53) + 1), 94906265);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(isqrt(u128::MAX - (1 << 65) + 2), u64::MAX as u128);
+        assert_eq!(isqrt(u128::MAX - (1 << 65) + 1), u64::MAX as u128 - 1);
+        assert_eq!(
+            isqrt(BigInt::from(u128::MAX) * BigInt::from(u128::MAX)),
+            BigInt::from(u128::MAX)
+        );
+
+        let root = (1u128 << 60) + 12345;
+        assert_eq!(isqrt(root * root - 1), root - 1);
+        assert_eq!(isqrt(root * root), root);
+    }
+
+    #[test]
+    fn positive_ranges() {
+        // x (7 - x) > 9
+        assert_eq!(quadratic_positive_range(-1, 7, -9), Some(2..=5));
+        // x (30 - x) > 200, touching the roots 10 and 20
+        assert_eq!(quadratic_positive_range(-1, 30, -200), Some(11..=19));
+        assert_eq!(quadratic_positive_range(-1, 0, 0), None);
+        assert_eq!(quadratic_positive_range(-2, 0, 1), Some(0..=0));
+        assert_eq!(quadratic_positive_range(-3, -20, 1), Some(-6..=0));
+
+        // x (T - x) > 0 and x (T - x) > T - 1, with T at the edge of u64
+        let time = i128::from(u64::MAX);
+        assert_eq!(quadratic_positive_range(-1, time, 0), Some(1..=time - 1));
+        assert_eq!(
+            quadratic_positive_range(-1, time, 1 - time),
+            Some(2..=time - 2)
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{Matrix, Position};
+
+type Idx = Position<usize>;
+
+/// The direction a line runs in, like a mirror line or a [`Span`](crate::Span).
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum Orientation {
+    /// Along the rows, e.g. a mirror between two rows or a span within one.
+    Horizontal,
+    /// Along the columns, e.g. a mirror between two columns or a span within one.
+    Vertical,
+}
+
+/// A mirror line and the cell pairs it fails to reflect onto each other.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Reflection {
+    pub orientation: Orientation,
+    /// The number of rows above (or columns left of) the mirror.
+    pub before: usize,
+    /// The mismatched cells, each with its mirror image; fixing a smudge on
+    /// either cell of a pair removes that mismatch.
+    pub smudges: Vec<(Idx, Idx)>,
+}
+
+impl Reflection {
+    pub fn mismatches(&self) -> usize {
+        self.smudges.len()
+    }
+
+    pub fn smudge_cells(&self) -> impl Iterator<Item = &Idx> {
+        self.smudges.iter().flat_map(|(a, b)| [a, b])
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: PartialEq,
+{
+    fn reflection(&self, orientation: Orientation, before: usize) -> Reflection {
+        let (lines, len) = match orientation {
+            Orientation::Horizontal => (self.rows, self.cols),
+            Orientation::Vertical => (self.cols, self.rows),
+        };
+        let at = |line: usize, offset: usize| -> Idx {
+            match orientation {
+                Orientation::Horizontal => (line, offset).into(),
+                Orientation::Vertical => (offset, line).into(),
+            }
+        };
+
+        let smudges = (0..before.min(lines - before))
+            .flat_map(|shift| {
+                let (a, b) = (before - shift, before + shift + 1);
+                (1..=len).map(move |offset| (at(a, offset), at(b, offset)))
+            })
+            .filter(|&(a, b)| self[a] != self[b])
+            .collect();
+
+        Reflection {
+            orientation,
+            before,
+            smudges,
+        }
+    }
+
+    /// Every mirror line between two rows or two columns, horizontal ones
+    /// first, with the cells it does not reflect.
+    pub fn reflections(&self) -> Vec<Reflection> {
+        let horizontal = (1..self.rows).map(|before| (Orientation::Horizontal, before));
+        let vertical = (1..self.cols).map(|before| (Orientation::Vertical, before));
+        horizontal
+            .chain(vertical)
+            .map(|(orientation, before)| self.reflection(orientation, before))
+            .collect()
+    }
+
+    /// The mirror lines that become perfect after fixing exactly `smudges` cells.
+    pub fn reflections_with_smudges(&self, smudges: usize) -> Vec<Reflection> {
+        self.reflections()
+            .into_iter()
+            .filter(|reflection| reflection.mismatches() == smudges)
+            .collect()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Orientation, Reflection};
+    use crate::Matrix;
+
+    #[test]
+    fn reflections() {
+        let matrix: Matrix<char> = "#.##\n#..#\n#..#\n#.#.".into();
+        let mismatches: Vec<usize> = matrix
+            .reflections()
+            .iter()
+            .map(Reflection::mismatches)
+            .collect();
+        assert_eq!(mismatches, vec![1, 1, 2, 4, 3, 3]);
+
+        assert!(matrix.reflections_with_smudges(0).is_empty());
+        assert_eq!(
+            matrix.reflections_with_smudges(1)[1],
+            Reflection {
+                orientation: Orientation::Horizontal,
+                before: 2,
+                smudges: vec![((1, 4).into(), (4, 4).into())],
+            }
+        );
+    }
+}
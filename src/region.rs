@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+
+use crate::{Matrix, Position};
+
+type Idx = Position<usize>;
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum Region {
+    Inside,
+    Outside,
+    Boundary,
+}
+
+/// Classifies every cell of a `rows` x `cols` grid against a closed loop of
+/// orthogonally adjacent cells, given in walking order.
+///
+/// Each row is scanned from left to right, toggling between outside and
+/// inside whenever the scanline crosses a loop cell connected to the cell
+/// above it.
+pub fn classify_regions(rows: usize, cols: usize, cycle: &[Idx]) -> Matrix<Region> {
+    let boundary: HashSet<&Idx> = cycle.iter().collect();
+    let connects_up: HashSet<&Idx> = (0..cycle.len())
+        .filter(|&index| {
+            let current = cycle[index];
+            let previous = cycle[(index + cycle.len() - 1) % cycle.len()];
+            let next = cycle[(index + 1) % cycle.len()];
+            [previous, next]
+                .iter()
+                .any(|other| other.col == current.col && other.row + 1 == current.row)
+        })
+        .map(|index| &cycle[index])
+        .collect();
+
+    (1..=rows)
+        .map(|row| {
+            let mut is_inside = false;
+            (1..=cols)
+                .map(|col| {
+                    let position: Idx = (row, col).into();
+                    if !boundary.contains(&position) {
+                        return match is_inside {
+                            true => Region::Inside,
+                            false => Region::Outside,
+                        };
+                    }
+                    if connects_up.contains(&position) {
+                        is_inside = !is_inside;
+                    }
+                    Region::Boundary
+                })
+                .collect()
+        })
+        .collect::<Vec<Vec<Region>>>()
+        .into()
+}
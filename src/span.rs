@@ -0,0 +1,134 @@
+use crate::{Matrix, Orientation, Position};
+
+type Idx = Position<usize>;
+
+/// `len` consecutive cells of a row (horizontal) or column (vertical),
+/// beginning at `start`.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub struct Span {
+    pub orientation: Orientation,
+    pub start: Idx,
+    pub len: usize,
+}
+
+impl Span {
+    pub fn positions(&self) -> impl Iterator<Item = Idx> + '_ {
+        (0..self.len).map(|offset| match self.orientation {
+            Orientation::Horizontal => (self.start.row, self.start.col + offset).into(),
+            Orientation::Vertical => (self.start.row + offset, self.start.col).into(),
+        })
+    }
+
+    pub fn end(&self) -> Idx {
+        self.positions().last().unwrap_or(self.start)
+    }
+
+    pub fn contains(&self, position: &Idx) -> bool {
+        let end = self.end();
+        (self.start.row..=end.row).contains(&position.row)
+            && (self.start.col..=end.col).contains(&position.col)
+    }
+}
+
+impl<T> Matrix<T> {
+    /// The maximal runs of cells matching `predicate` along every row
+    /// (horizontal) or column (vertical), one line after the other.
+    pub fn runs<F>(&self, orientation: Orientation, mut predicate: F) -> Vec<Span>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let (lines, len) = match orientation {
+            Orientation::Horizontal => (self.rows, self.cols),
+            Orientation::Vertical => (self.cols, self.rows),
+        };
+        let at = |line: usize, offset: usize| -> Idx {
+            match orientation {
+                Orientation::Horizontal => (line, offset).into(),
+                Orientation::Vertical => (offset, line).into(),
+            }
+        };
+
+        let mut spans = vec![];
+        (1..=lines).for_each(|line| {
+            let mut start = None;
+            (1..=len + 1).for_each(|offset| {
+                let matches = offset <= len && predicate(&self[at(line, offset)]);
+                match (start, matches) {
+                    (None, true) => start = Some(offset),
+                    (Some(run_start), false) => {
+                        spans.push(Span {
+                            orientation,
+                            start: at(line, run_start),
+                            len: offset - run_start,
+                        });
+                        start = None;
+                    }
+                    _ => {}
+                }
+            });
+        });
+        spans
+    }
+
+    pub fn span_values(&self, span: &Span) -> Vec<&T> {
+        span.positions().map(|position| &self[position]).collect()
+    }
+
+    /// The cells around `span`, diagonal neighbors included, in reading order.
+    pub fn span_border(&self, span: &Span) -> Vec<Idx> {
+        let end = span.end();
+        (span.start.row - 1..=end.row + 1)
+            .flat_map(|row| (span.start.col - 1..=end.col + 1).map(move |col| (row, col).into()))
+            .filter(|position| self.is_valid_position(position) && !span.contains(position))
+            .collect()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Span;
+    use crate::{Matrix, Orientation};
+
+    #[test]
+    fn runs() {
+        let matrix: Matrix<char> = "12.\n.3*\n45.".into();
+        let digit = |ch: &char| ch.is_ascii_digit();
+
+        let spans = matrix.runs(Orientation::Horizontal, digit);
+        let numbers: Vec<String> = spans
+            .iter()
+            .map(|span| matrix.span_values(span).into_iter().collect())
+            .collect();
+        assert_eq!(numbers, vec!["12", "3", "45"]);
+
+        let spans = matrix.runs(Orientation::Vertical, digit);
+        assert_eq!(
+            spans[2],
+            Span {
+                orientation: Orientation::Vertical,
+                start: (1, 2).into(),
+                len: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn border() {
+        let matrix: Matrix<char> = "12.\n.3*\n45.".into();
+        let span = matrix.runs(Orientation::Horizontal, char::is_ascii_digit)[0];
+        assert_eq!(
+            matrix.span_border(&span),
+            vec![(1, 3).into(), (2, 1).into(), (2, 2).into(), (2, 3).into()]
+        );
+
+        let span = Span {
+            orientation: Orientation::Vertical,
+            start: (2, 3).into(),
+            len: 2,
+        };
+        assert_eq!(
+            matrix.span_border(&span),
+            vec![(1, 2).into(), (1, 3).into(), (2, 2).into(), (3, 2).into()]
+        );
+    }
+}
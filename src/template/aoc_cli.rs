@@ -4,6 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
+use crate::template::description;
 use crate::Day;
 
 #[derive(Debug)]
@@ -87,10 +88,10 @@ fn get_input_path(day: Day) -> String {
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    description::get_path_for_description(day)
 }
 
-fn get_year() -> Option<u16> {
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -128,6 +128,7 @@ mod child_commands {
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
         let mut timings = super::Timings {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
@@ -149,7 +150,9 @@ mod child_commands {
                 Some((part, timing_str, nanos))
             })
             .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
+                if part.contains("Parse") {
+                    timings.parse = Some(timing_str.into());
+                } else if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
@@ -171,7 +174,7 @@ mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -221,6 +224,23 @@ mod child_commands {
             assert_eq!(res.part_2.unwrap(), "74.13ms");
         }
 
+        #[test]
+        fn test_parse_timing() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (1.5µs @ 10000 samples)".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: ✖        ".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 1574.13_f64);
+            assert_eq!(res.parse.unwrap(), "1.5µs");
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert!(res.part_2.is_none());
+        }
+
         #[test]
         fn test_patterns_in_input() {
             let res = parse_exec_time(
@@ -0,0 +1,186 @@
+use std::{fs, path::Path, process};
+
+use crate::template::{
+    commands::scaffold::replace_tests,
+    description::{self, Example},
+};
+use crate::Day;
+
+const PART_NAMES: [&str; 2] = ["one", "two"];
+
+/// The example files of `day`: `DD.txt` for a single example, and numbered
+/// ones like `DD-1.txt` otherwise.
+fn example_paths(day: Day, count: usize) -> Vec<String> {
+    match count {
+        1 => vec![format!("data/examples/{day}.txt")],
+        _ => (1..=count)
+            .map(|number| format!("data/examples/{day}-{number}.txt"))
+            .collect(),
+    }
+}
+
+/// The argument reading example `number` of `count` in a test, laid out
+/// the way rustfmt formats it.
+fn read_file_expr(count: usize, number: usize) -> String {
+    let template = "&advent_of_code::template";
+    match count {
+        1 => format!("{template}::read_file(\"examples\", DAY)"),
+        _ => [
+            format!("{template}::read_file_part("),
+            format!("            \"examples\", DAY, {number},"),
+            "        )".to_string(),
+        ]
+        .join("\n"),
+    }
+}
+
+/// A test module with a test for every part that has an answer, asserting
+/// it for every example that gives one.
+#[must_use]
+pub fn generate_tests(examples: &[Example]) -> String {
+    let tests: Vec<String> = PART_NAMES
+        .iter()
+        .enumerate()
+        .filter_map(|(part, name)| {
+            let cases: Vec<String> = examples
+                .iter()
+                .enumerate()
+                .filter_map(|(index, example)| {
+                    let answer = example.answers[part].as_ref()?;
+                    let read_file = read_file_expr(examples.len(), index + 1);
+                    Some(
+                        [
+                            format!("        let result = part_{name}({read_file});"),
+                            format!("        assert_eq!(result, Some({answer}));"),
+                            String::new(),
+                        ]
+                        .join("\n"),
+                    )
+                })
+                .collect();
+            (!cases.is_empty()).then(|| {
+                format!(
+                    "    #[test]\n    fn test_part_{name}() {{\n{}    }}\n",
+                    cases.join("\n")
+                )
+            })
+        })
+        .collect();
+
+    format!(
+        "#[cfg(test)]\nmod tests {{\n    use super::*;\n\n{}}}\n",
+        tests.join("\n")
+    )
+}
+
+pub fn handle(day: Day, force: bool) {
+    let Some(description) = description::read(day) else {
+        eprintln!(
+            "Could not read \"{}\". Try running \"cargo download {}\" first.",
+            description::get_path_for_description(day),
+            day
+        );
+        process::exit(1);
+    };
+
+    let examples = description::examples(&description);
+    if examples.is_empty() {
+        eprintln!("Could not find any examples in the description.");
+        process::exit(1);
+    }
+
+    for (path, example) in example_paths(day, examples.len()).iter().zip(&examples) {
+        let is_empty = fs::read_to_string(path).map_or(true, |text| text.trim().is_empty());
+        if !is_empty && !force {
+            println!("Skipped existing example file \"{path}\", pass --force to overwrite it.");
+            continue;
+        }
+        match fs::write(path, &example.input) {
+            Ok(()) => println!("Wrote example file \"{path}\""),
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let module_path = format!("src/bin/{day}.rs");
+    if !Path::new(&module_path).exists() {
+        println!(
+            "No module file \"{module_path}\" to add tests to, run \"cargo scaffold {day}\" first."
+        );
+        return;
+    }
+    let module = match fs::read_to_string(&module_path) {
+        Ok(module) => module,
+        Err(e) => {
+            eprintln!("Failed to read module file: {e}");
+            process::exit(1);
+        }
+    };
+    match fs::write(
+        &module_path,
+        replace_tests(&module, &generate_tests(&examples)),
+    ) {
+        Ok(()) => println!("Updated tests in module file \"{module_path}\""),
+        Err(e) => {
+            eprintln!("Failed to write module file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::generate_tests;
+    use crate::template::description::Example;
+
+    fn example(input: &str, part_one: Option<&str>, part_two: Option<&str>) -> Example {
+        Example {
+            input: input.into(),
+            answers: [part_one.map(String::from), part_two.map(String::from)],
+        }
+    }
+
+    #[test]
+    fn single_example() {
+        let tests = generate_tests(&[example("1\n", Some("142"), Some("281"))]);
+        assert_eq!(
+            tests,
+            [
+                "#[cfg(test)]",
+                "mod tests {",
+                "    use super::*;",
+                "",
+                "    #[test]",
+                "    fn test_part_one() {",
+                "        let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));",
+                "        assert_eq!(result, Some(142));",
+                "    }",
+                "",
+                "    #[test]",
+                "    fn test_part_two() {",
+                "        let result = part_two(&advent_of_code::template::read_file(\"examples\", DAY));",
+                "        assert_eq!(result, Some(281));",
+                "    }",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn numbered_examples() {
+        let tests = generate_tests(&[
+            example("1\n", None, Some("94")),
+            example("2\n", None, None),
+            example("3\n", None, Some("71")),
+        ]);
+        assert!(!tests.contains("fn test_part_one()"));
+        assert!(tests.contains(
+            "        let result = part_two(&advent_of_code::template::read_file_part(\n            \"examples\", DAY, 3,\n        ));\n        assert_eq!(result, Some(71));\n"
+        ));
+        assert!(!tests.contains("DAY, 2,"));
+    }
+}
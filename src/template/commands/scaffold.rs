@@ -1,70 +1,211 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    ops::Range,
+    path::Path,
     process,
 };
 
+use crate::template::{aoc_cli, description};
 use crate::Day;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// Where user-defined templates are looked up, as `<name>.rs`.
+const TEMPLATE_DIR: &str = "templates";
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+pub const DEFAULT_TEMPLATE: &str = "lines";
+
+/// The templates shipped with the repository, used when one of the same
+/// name is missing from [`TEMPLATE_DIR`].
+const BUILTIN_TEMPLATES: [(&str, &str); 3] = [
+    ("lines", include_str!("../../../templates/lines.rs")),
+    ("grid", include_str!("../../../templates/grid.rs")),
+    ("graph", include_str!("../../../templates/graph.rs")),
+];
+
+const TESTS_MARKER: &str = "#[cfg(test)]";
+const TEST_PREFIX: &str = "    #[test]\n    fn ";
+const TEST_END: &str = "\n    }";
+
+/// What to do if the module of the day already exists.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Existing {
+    Refuse,
+    Overwrite,
+    /// Replace the tests only, keeping the solution.
+    UpdateTests,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub struct Options {
+    pub template: String,
+    pub existing: Existing,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// The values filled into `{{DAY}}`, `{{YEAR}}`, `{{TITLE}}`,
+/// `{{PART_ONE_ANSWER}}` and `{{PART_TWO_ANSWER}}`. Lines using `{{YEAR}}`
+/// are left out when the year is unknown.
+pub struct Placeholders {
+    pub day: Day,
+    pub year: Option<u16>,
+    pub title: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+impl Placeholders {
+    #[must_use]
+    pub fn render(&self, template: &str) -> String {
+        let answer = |answer: &Option<String>| match answer {
+            Some(answer) => format!("Some({answer})"),
+            None => "None".into(),
+        };
+        let template: String = match self.year {
+            Some(_) => template.into(),
+            None => template
+                .split_inclusive('\n')
+                .filter(|line| !line.contains("{{YEAR}}"))
+                .collect(),
+        };
+        template
+            .replace("{{DAY}}", &self.day.into_inner().to_string())
+            .replace(
+                "{{YEAR}}",
+                &self.year.map(|year| year.to_string()).unwrap_or_default(),
+            )
+            .replace("{{TITLE}}", &self.title)
+            .replace("{{PART_ONE_ANSWER}}", &answer(&self.part_one))
+            .replace("{{PART_TWO_ANSWER}}", &answer(&self.part_two))
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+pub fn load_template(name: &str) -> Result<String, String> {
+    let path = Path::new(TEMPLATE_DIR).join(format!("{name}.rs"));
+    if let Ok(template) = fs::read_to_string(&path) {
+        return Ok(template);
     }
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| template.to_string())
+        .ok_or_else(|| format!("Could not find template \"{}\".", path.display()))
+}
+
+/// The names of the `#[test]` functions in `module`, assuming it is
+/// formatted by rustfmt.
+fn test_names(module: &str) -> Vec<&str> {
+    module
+        .match_indices(TEST_PREFIX)
+        .filter_map(|(index, _)| {
+            let name = &module[index + TEST_PREFIX.len()..];
+            name.split_once('(').map(|(name, _)| name)
+        })
+        .collect()
+}
+
+/// The byte range of the `#[test]` function `name`, its attribute included.
+fn find_test(module: &str, name: &str) -> Option<Range<usize>> {
+    let start = module.find(&format!("{TEST_PREFIX}{name}("))?;
+    let end = start + module[start..].find(TEST_END)? + TEST_END.len();
+    Some(start..end)
+}
+
+/// `module` with the tests of `rendered` in place of the ones of the same
+/// name, and the others added at the end of its tests. Tests only found in
+/// `module` are kept.
+#[must_use]
+pub fn replace_tests(module: &str, rendered: &str) -> String {
+    let Some(marker) = module.find(TESTS_MARKER) else {
+        let tests = rendered
+            .find(TESTS_MARKER)
+            .map_or("", |index| &rendered[index..]);
+        return format!("{}\n\n{}", module.trim_end(), tests);
+    };
+
+    let mut module = module.to_string();
+    test_names(rendered).into_iter().for_each(|name| {
+        let test = &rendered[find_test(rendered, name).unwrap()];
+        match find_test(&module, name) {
+            Some(range) => module.replace_range(range, test),
+            None => {
+                let end = module.trim_end().rfind('}').filter(|&end| end > marker);
+                module.insert_str(end.unwrap_or(module.len()), &format!("\n{test}\n"));
+            }
+        }
+    });
+    module
 }
-"#;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+fn write_module(path: &str, rendered: &str, existing: Existing) -> Result<&'static str, String> {
+    if !Path::new(path).exists() {
+        let mut file = safe_create_file(path).map_err(|e| e.to_string())?;
+        file.write_all(rendered.as_bytes())
+            .map_err(|e| e.to_string())?;
+        return Ok("Created");
+    }
+
+    match existing {
+        Existing::Refuse => Err(
+            "Module file already exists. Pass --force to overwrite it or --update-tests to only regenerate its tests.".into(),
+        ),
+        Existing::Overwrite => fs::write(path, rendered)
+            .map(|()| "Overwrote")
+            .map_err(|e| e.to_string()),
+        Existing::UpdateTests => {
+            let module = fs::read_to_string(path).map_err(|e| e.to_string())?;
+            fs::write(path, replace_tests(&module, rendered))
+                .map(|()| "Updated tests in")
+                .map_err(|e| e.to_string())
+        }
+    }
 }
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, options: Options) {
     let input_path = format!("data/inputs/{day}.txt");
     let example_path = format!("data/examples/{day}.txt");
     let module_path = format!("src/bin/{day}.rs");
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+    let template = match load_template(&options.template) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to load template: {e}");
             process::exit(1);
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
+    let title = description::read(day)
+        .and_then(|description| description::title(&description).map(String::from))
+        .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+    let placeholders = Placeholders {
+        day,
+        year: aoc_cli::get_year(),
+        title,
+        part_one: options.part_one,
+        part_two: options.part_two,
+    };
+
+    match write_module(
+        &module_path,
+        &placeholders.render(&template),
+        options.existing,
     ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+        Ok(action) => {
+            println!("{} module file \"{}\"", action, &module_path);
         }
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to write module file: {e}");
             process::exit(1);
         }
     }
@@ -92,3 +233,98 @@ pub fn handle(day: Day) {
     println!("---");
     println!("🎄 Type `cargo solve {}` to run your solution.", day);
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load_template, replace_tests, Placeholders};
+    use crate::day;
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(7),
+            year: Some(2023),
+            title: "Camel Cards".into(),
+            part_one: Some("6440".into()),
+            part_two: None,
+        };
+        assert_eq!(
+            placeholders
+                .render("// {{TITLE}} {{YEAR}}/{{DAY}}: {{PART_ONE_ANSWER}}, {{PART_TWO_ANSWER}}"),
+            "// Camel Cards 2023/7: Some(6440), None"
+        );
+
+        let rendered = placeholders.render(&load_template("grid").unwrap());
+        assert!(rendered.contains("advent_of_code::solution!(7);"));
+        assert!(rendered.contains("assert_eq!(result, Some(6440));"));
+        assert!(!rendered.contains("{{"));
+        assert!(rendered.starts_with("// Camel Cards\n// https://adventofcode.com/2023/day/7\n"));
+        assert!(load_template("unknown").is_err());
+
+        let placeholders = Placeholders {
+            year: None,
+            ..placeholders
+        };
+        let rendered = placeholders.render(&load_template("lines").unwrap());
+        assert!(rendered.starts_with("// Camel Cards\nuse advent_of_code::Puzzle;\n"));
+        assert!(!rendered.contains("adventofcode.com"));
+    }
+
+    #[test]
+    fn replaces_tests() {
+        let rendered = [
+            "fn todo() {}",
+            "",
+            "#[cfg(test)]",
+            "mod tests {",
+            "    #[test]",
+            "    fn test_part_one() {",
+            "        assert_eq!(part_one(), Some(1));",
+            "    }",
+            "",
+            "    #[test]",
+            "    fn test_part_two() {",
+            "        assert_eq!(part_two(), Some(2));",
+            "    }",
+            "}",
+            "",
+        ]
+        .join("\n");
+        let module = [
+            "fn solve() {}",
+            "",
+            "#[cfg(test)]",
+            "mod tests {",
+            "    #[test]",
+            "    fn test_part_one() {",
+            "        assert_eq!(part_one(), None);",
+            "    }",
+            "",
+            "    #[test]",
+            "    fn test_helper() {",
+            "        assert!(true);",
+            "    }",
+            "}",
+            "",
+        ]
+        .join("\n");
+
+        let updated = replace_tests(&module, &rendered);
+        assert!(updated.starts_with("fn solve() {}\n"));
+        assert!(updated.contains("assert_eq!(part_one(), Some(1));"));
+        assert!(!updated.contains("assert_eq!(part_one(), None);"));
+        assert!(updated.contains("    fn test_helper() {"));
+        assert!(updated.ends_with(
+            "    fn test_part_two() {\n        assert_eq!(part_two(), Some(2));\n    }\n}\n"
+        ));
+        assert_eq!(replace_tests(&updated, &rendered), updated);
+
+        assert_eq!(
+            replace_tests("fn solve() {}\n", &rendered),
+            format!(
+                "fn solve() {{}}\n\n{}",
+                &rendered[rendered.find("#[cfg").unwrap()..]
+            )
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Reads the puzzle descriptions saved by `cargo download` to `data/puzzles`.
+use std::fs;
+
+use crate::Day;
+
+#[must_use]
+pub fn get_path_for_description(day: Day) -> String {
+    format!("data/puzzles/{day}.md")
+}
+
+/// The description of `day`, if it was downloaded.
+pub fn read(day: Day) -> Option<String> {
+    fs::read_to_string(get_path_for_description(day)).ok()
+}
+
+/// Whether `line` underlines the heading above it, like aoc-cli's `----------`.
+fn is_setext_underline(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && (line.chars().all(|ch| ch == '-') || line.chars().all(|ch| ch == '='))
+}
+
+/// The title from a heading like `\--- Day 1: Trebuchet?! ---` followed by an
+/// underline, as aoc-cli writes it, or `## --- Day 1: Trebuchet?! ---`.
+pub fn title(description: &str) -> Option<&str> {
+    let lines: Vec<&str> = description.lines().collect();
+    lines.iter().enumerate().find_map(|(index, line)| {
+        let heading = match line.strip_prefix('#') {
+            Some(heading) => heading.trim_start_matches('#'),
+            None if lines
+                .get(index + 1)
+                .is_some_and(|next| is_setext_underline(next)) =>
+            {
+                line
+            }
+            None => return None,
+        };
+        let heading = heading.trim().trim_start_matches('\\');
+        let heading = heading.strip_prefix("--- Day ")?.strip_suffix(" ---")?;
+        Some(heading.split_once(": ")?.1)
+    })
+}
+
+const FENCE: &str = "```";
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// An example input and the answers the description gives for it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Example {
+    pub input: String,
+    pub answers: [Option<String>; 2],
+}
+
+/// A code block and the prose up to the next one.
+struct Block<'a> {
+    /// The last prose line before the block.
+    intro: &'a str,
+    code: String,
+    after: Vec<&'a str>,
+}
+
+fn blocks(section: &str) -> Vec<Block<'_>> {
+    let mut blocks: Vec<Block> = vec![];
+    let mut intro = "";
+    let mut code: Option<Vec<&str>> = None;
+    section
+        .lines()
+        .for_each(|line| match (&mut code, line.starts_with(FENCE)) {
+            (None, true) => code = Some(vec![]),
+            (Some(lines), true) => {
+                blocks.push(Block {
+                    intro,
+                    code: lines.iter().map(|line| format!("{line}\n")).collect(),
+                    after: vec![],
+                });
+                code = None;
+            }
+            (Some(lines), false) => lines.push(line),
+            (None, false) => {
+                if let Some(block) = blocks.last_mut() {
+                    block.after.push(line);
+                }
+                if !line.trim().is_empty() {
+                    intro = line;
+                }
+            }
+        });
+    blocks
+}
+
+/// The emphasized spans of `line`, like `*142*`, `**142**`, `*`142`*` or
+/// `` `*142*` ``, without their markup.
+fn emphasized(line: &str) -> Vec<&str> {
+    let mut spans = vec![];
+    let mut rest = line;
+    while let Some(start) = rest.find(['*', '`']) {
+        let delimiter = match &rest[start..] {
+            tail if tail.starts_with("**") => "**",
+            tail if tail.starts_with('*') => "*",
+            _ => "`",
+        };
+        let inner = &rest[start + delimiter.len()..];
+        let Some(end) = inner.find(delimiter) else {
+            break;
+        };
+        let span = &inner[..end];
+        let is_code = delimiter == "`";
+        if !is_code || (span.len() > 2 && span.starts_with('*') && span.ends_with('*')) {
+            spans.push(span.trim_matches(['*', '`']));
+        }
+        rest = &inner[end + delimiter.len()..];
+    }
+    spans
+}
+
+fn is_answer(span: &str) -> bool {
+    let digits = span.strip_prefix('-').unwrap_or(span);
+    !digits.is_empty() && digits.chars().all(|ch| ch.is_ascii_digit())
+}
+
+/// The examples of both parts. A code block counts as an example if it is
+/// the first one, or if the prose right before it mentions an example; its
+/// answer is the last emphasized number before the next example. A part
+/// without examples of its own answers about the first one.
+pub fn examples(description: &str) -> Vec<Example> {
+    let (part_one, part_two) = match description.find(PART_TWO_HEADING) {
+        Some(index) => description.split_at(index),
+        None => (description, ""),
+    };
+
+    let mut examples: Vec<Example> = vec![];
+    [part_one, part_two]
+        .into_iter()
+        .enumerate()
+        .for_each(|(part, section)| {
+            let mut last_answer = None;
+            let mut found = false;
+            blocks(section)
+                .into_iter()
+                .enumerate()
+                .for_each(|(index, block)| {
+                    let is_example =
+                        (part == 0 && index == 0) || block.intro.to_lowercase().contains("example");
+                    if is_example {
+                        found = true;
+                        let position = match examples
+                            .iter()
+                            .position(|example| example.input == block.code)
+                        {
+                            Some(position) => position,
+                            None => {
+                                examples.push(Example {
+                                    input: block.code,
+                                    answers: [None, None],
+                                });
+                                examples.len() - 1
+                            }
+                        };
+                        last_answer = Some(position);
+                    }
+                    let answer = block
+                        .after
+                        .iter()
+                        .flat_map(|line| emphasized(line))
+                        .rev()
+                        .find(|span| is_answer(span));
+                    if let (Some(position), Some(answer)) = (last_answer, answer) {
+                        examples[position].answers[part] = Some(answer.to_string());
+                    }
+                });
+
+            if !found && !examples.is_empty() {
+                let answer = section
+                    .lines()
+                    .flat_map(emphasized)
+                    .rev()
+                    .find(|span| is_answer(span));
+                if let Some(answer) = answer {
+                    examples[0].answers[part] = Some(answer.to_string());
+                }
+            }
+        });
+    examples
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasized, examples, title, Example};
+
+    #[test]
+    fn finds_title() {
+        let description = [
+            "\\--- Day 1: Trebuchet?! ---",
+            "----------",
+            "",
+            "Something is wrong with global snow production.",
+        ]
+        .join("\n");
+        assert_eq!(title(&description), Some("Trebuchet?!"));
+        assert_eq!(
+            title("## --- Day 7: Camel Cards ---\nYour all-expenses-paid trip"),
+            Some("Camel Cards")
+        );
+        assert_eq!(title("\\--- Day 1: Trebuchet?! ---\nNot underlined."), None);
+        assert_eq!(title("## Day 1"), None);
+    }
+
+    #[test]
+    fn emphasized_spans() {
+        assert_eq!(
+            emphasized("Adding these gives *`142`*, or **281**, not `*`."),
+            vec!["142", "281"]
+        );
+        assert_eq!(emphasized("Run `*7*` times with a *lone"), vec!["7"]);
+    }
+
+    #[test]
+    fn finds_examples() {
+        let description = [
+            "\\--- Day 1: Trebuchet?! ---",
+            "----------",
+            "",
+            "For example:",
+            "",
+            "```",
+            "1abc2",
+            "```",
+            "",
+            "In this example, the values are `12` and *3*. Adding these gives `*142*`.",
+            "",
+            "\\--- Part Two ---",
+            "----------",
+            "",
+            "For example:",
+            "",
+            "```",
+            "two1nine",
+            "```",
+            "",
+            "Adding these gives `*281*`.",
+        ]
+        .join("\n");
+        assert_eq!(
+            examples(&description),
+            vec![
+                Example {
+                    input: "1abc2\n".into(),
+                    answers: [Some("142".into()), None],
+                },
+                Example {
+                    input: "two1nine\n".into(),
+                    answers: [None, Some("281".into())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn shares_examples() {
+        let description = [
+            "Here is an example:",
+            "```",
+            "O.#",
+            "```",
+            "Tilting gives:",
+            "```",
+            "#.O",
+            "```",
+            "The total load is *`136`*.",
+            "## --- Part Two ---",
+            "After *3* cycles:",
+            "```",
+            "..#",
+            "```",
+            "In the above example, the total load is *`64`*.",
+        ]
+        .join("\n");
+        assert_eq!(
+            examples(&description),
+            vec![Example {
+                input: "O.#\n".into(),
+                answers: [Some("136".into()), Some("64".into())],
+            }]
+        );
+    }
+}
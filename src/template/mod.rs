@@ -3,6 +3,7 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod description;
 pub mod readme_benchmarks;
 pub mod runner;
 
@@ -31,18 +32,35 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `DAY` and sets up the input and runner for the day's
+/// [`Puzzle`](crate::Puzzle), `Solution` unless another type is given.
+///
+/// Also defines `part_one` and `part_two`, which parse their input before
+/// solving, for the tests.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
+        advent_of_code::solution!($day, Solution);
+    };
+    ($day:expr, $puzzle:ty) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        #[allow(dead_code)]
+        fn part_one(input: &str) -> Option<<$puzzle as advent_of_code::Puzzle>::PartOne> {
+            use advent_of_code::Puzzle;
+            <$puzzle>::part_one(&<$puzzle>::parse(input))
+        }
+
+        #[allow(dead_code)]
+        fn part_two(input: &str) -> Option<<$puzzle as advent_of_code::Puzzle>::PartTwo> {
+            use advent_of_code::Puzzle;
+            <$puzzle>::part_two(&<$puzzle>::parse(input))
+        }
+
         fn main() {
-            use advent_of_code::template::runner::*;
             let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            advent_of_code::template::runner::run_puzzle::<$puzzle>(&input, DAY);
         }
     };
 }
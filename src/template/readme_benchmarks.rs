@@ -21,6 +21,7 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
@@ -65,16 +66,17 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
+            timing.parse.unwrap_or_else(|| "-".into()),
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
         ));
@@ -111,18 +113,21 @@ mod tests {
         vec![
             Timings {
                 day: day!(1),
+                parse: Some("5ms".into()),
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
+                parse: Some("15ms".into()),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
+                parse: Some("25ms".into()),
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
                 total_nanos: 9e+10,
@@ -170,11 +175,11 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `5ms` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `15ms` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `25ms` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
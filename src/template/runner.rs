@@ -1,6 +1,6 @@
 /// Encapsulates code that interacts with solution functions.
 use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::{visualize, Day, Puzzle};
 use std::fmt::Display;
 use std::io::{stdout, Write};
 use std::process::Output;
@@ -9,6 +9,26 @@ use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
+/// Parses the input once, then runs both parts on the parsed value, timing
+/// every step on its own. Exports are written afterwards, untimed.
+pub fn run_puzzle<P: Puzzle>(input: &str, day: Day) {
+    let (parsed, duration, samples) = run_timed(P::parse, input, |_| print!("Parse:"));
+    print!("\r");
+    println!("Parse:{}", format_duration(&duration, samples));
+    if let Some(diagnostic) = P::diagnostic(&parsed) {
+        eprintln!("{diagnostic}");
+    }
+
+    run_part(|parsed| P::part_one(parsed), &parsed, day, 1);
+    run_part(|parsed| P::part_two(parsed), &parsed, day, 2);
+
+    if visualize::output_dir().is_some() {
+        if let Err(e) = P::export(&parsed) {
+            eprintln!("Failed to export day {day}: {e}");
+        }
+    }
+}
+
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
@@ -51,13 +71,8 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -0,0 +1,418 @@
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    io::{self, stdout, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use crate::{template::ANSI_RESET, Matrix, Position};
+
+type Idx = Position<usize>;
+
+/// The output directory named by the `AOC_RECORD` environment variable.
+pub fn output_dir() -> Option<PathBuf> {
+    env::var_os("AOC_RECORD").map(PathBuf::from)
+}
+
+/// Writes a text export, such as a trace or a graph, as `file_name` in the
+/// `AOC_RECORD` directory, if set.
+pub fn export_text(file_name: &str, contents: &str) -> io::Result<()> {
+    match output_dir() {
+        Some(dir) => {
+            fs::create_dir_all(&dir)?;
+            fs::write(dir.join(file_name), contents)
+        }
+        None => Ok(()),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+    pub const GRAY: Self = Self::new(128, 128, 128);
+    pub const RED: Self = Self::new(220, 50, 47);
+    pub const GREEN: Self = Self::new(133, 153, 0);
+    pub const BLUE: Self = Self::new(38, 139, 210);
+    pub const YELLOW: Self = Self::new(181, 137, 0);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+}
+
+impl Style {
+    pub fn foreground(color: Color) -> Self {
+        Self {
+            foreground: Some(color),
+            background: None,
+        }
+    }
+
+    pub fn background(color: Color) -> Self {
+        Self {
+            foreground: None,
+            background: Some(color),
+        }
+    }
+
+    /// Layers `other` on top of `self`, keeping colors `other` leaves unset.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            foreground: other.foreground.or(self.foreground),
+            background: other.background.or(self.background),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Glyph {
+    pub symbol: char,
+    pub style: Style,
+}
+
+impl From<char> for Glyph {
+    fn from(symbol: char) -> Self {
+        Self {
+            symbol,
+            style: Style::default(),
+        }
+    }
+}
+
+/// A styled snapshot of a [`Matrix`], renderable to the terminal or to SVG.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Frame {
+    glyphs: Matrix<Glyph>,
+}
+
+impl<T> From<&Matrix<T>> for Frame
+where
+    T: Copy + Into<char>,
+{
+    fn from(matrix: &Matrix<T>) -> Self {
+        Self::new(matrix, |_, &cell| cell.into())
+    }
+}
+
+impl Frame {
+    pub fn new<T, G, F>(matrix: &Matrix<T>, mut f: F) -> Self
+    where
+        G: Into<Glyph>,
+        F: FnMut(Idx, &T) -> G,
+    {
+        Self {
+            glyphs: matrix.map_with_position(|position, cell| f(position, cell).into()),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.glyphs.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.glyphs.cols
+    }
+
+    pub fn set_symbol(&mut self, position: Idx, symbol: char) -> &mut Self {
+        self.glyphs[position].symbol = symbol;
+        self
+    }
+
+    /// Applies `style` to every valid position, e.g. a path or a visited set.
+    pub fn highlight<'a, I>(&mut self, positions: I, style: Style) -> &mut Self
+    where
+        I: IntoIterator<Item = &'a Idx>,
+    {
+        for &position in positions {
+            if self.glyphs.is_valid_position(&position) {
+                let glyph = &mut self.glyphs[position];
+                glyph.style = glyph.style.merge(style);
+            }
+        }
+        self
+    }
+
+    pub fn to_ansi(&self) -> String {
+        let mut output = String::new();
+        for row in self.glyphs.row_iter() {
+            for Glyph { symbol, style } in row {
+                if let Some(Color { r, g, b }) = style.foreground {
+                    let _ = write!(output, "\x1b[38;2;{r};{g};{b}m");
+                }
+                if let Some(Color { r, g, b }) = style.background {
+                    let _ = write!(output, "\x1b[48;2;{r};{g};{b}m");
+                }
+                output.push(*symbol);
+                if *style != Style::default() {
+                    output.push_str(ANSI_RESET);
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    pub fn to_svg(&self, cell_size: usize) -> String {
+        let mut output = svg_header(self.cols() * cell_size, self.rows() * cell_size);
+        output.push_str(&self.svg_cells(cell_size));
+        output.push_str("</svg>\n");
+        output
+    }
+
+    fn svg_cells(&self, cell_size: usize) -> String {
+        let mut output = String::new();
+        for (position, glyph) in self.glyphs.indexes().into_iter().zip(self.glyphs.iter()) {
+            let x = (position.col - 1) * cell_size;
+            let y = (position.row - 1) * cell_size;
+            let background = glyph.style.background.unwrap_or(Color::WHITE).to_hex();
+            let foreground = glyph.style.foreground.unwrap_or(Color::BLACK).to_hex();
+            let _ = writeln!(
+                output,
+                r#"<rect x="{x}" y="{y}" width="{cell_size}" height="{cell_size}" fill="{background}"/>"#
+            );
+            if !glyph.symbol.is_whitespace() {
+                let _ = writeln!(
+                    output,
+                    r#"<text x="{}" y="{}" fill="{foreground}">{}</text>"#,
+                    x + cell_size / 2,
+                    y + cell_size / 2,
+                    escape_xml(glyph.symbol),
+                );
+            }
+        }
+        output
+    }
+}
+
+fn svg_header(width: usize, height: usize) -> String {
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" "#,
+            r#"font-family="monospace" text-anchor="middle" dominant-baseline="central">"#,
+            "\n"
+        ),
+        width, height
+    )
+}
+
+fn escape_xml(symbol: char) -> String {
+    match symbol {
+        '&' => "&amp;".into(),
+        '<' => "&lt;".into(),
+        '>' => "&gt;".into(),
+        _ => symbol.into(),
+    }
+}
+
+/// Collects frames during a simulation so they can be replayed or exported offline.
+///
+/// A disabled recorder never builds frames, so solutions can keep their
+/// `record` calls in place without paying for them.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    frames: Vec<Frame>,
+    output: Option<PathBuf>,
+    enabled: bool,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Enables recording when the `AOC_RECORD` environment variable names an
+    /// output directory for [`Recorder::save`].
+    pub fn from_env() -> Self {
+        match output_dir() {
+            Some(output) => Self {
+                output: Some(output),
+                ..Self::new()
+            },
+            None => Self::disabled(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn record<F>(&mut self, frame: F)
+    where
+        F: FnOnce() -> Frame,
+    {
+        if self.enabled {
+            self.frames.push(frame());
+        }
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Replays the recorded frames in the terminal.
+    pub fn play(&self, delay: Duration) {
+        let mut stdout = stdout();
+        for frame in &self.frames {
+            print!("\x1b[H\x1b[2J{}", frame.to_ansi());
+            let _ = stdout.flush();
+            thread::sleep(delay);
+        }
+    }
+
+    /// Writes every frame as `frame_NNNN.svg` into `dir`.
+    pub fn save_frames(&self, dir: &Path, cell_size: usize) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for (index, frame) in self.frames.iter().enumerate() {
+            fs::write(
+                dir.join(format!("frame_{index:04}.svg")),
+                frame.to_svg(cell_size),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes all frames into a single SVG file that shows them one after another.
+    pub fn save_animation(&self, path: &Path, cell_size: usize, delay: Duration) -> io::Result<()> {
+        let (rows, cols) = self.frames.iter().fold((0, 0), |(rows, cols), frame| {
+            (rows.max(frame.rows()), cols.max(frame.cols()))
+        });
+        let delay = delay.as_secs_f64();
+
+        let mut output = svg_header(cols * cell_size, rows * cell_size);
+        for (index, frame) in self.frames.iter().enumerate() {
+            let is_last = index + 1 == self.frames.len();
+            let _ = writeln!(
+                output,
+                r#"<g visibility="hidden"><set attributeName="visibility" to="visible" begin="{}s"{}/>"#,
+                index as f64 * delay,
+                if is_last {
+                    String::new()
+                } else {
+                    format!(r#" dur="{delay}s""#)
+                }
+            );
+            output.push_str(&frame.svg_cells(cell_size));
+            output.push_str("</g>\n");
+        }
+        output.push_str("</svg>\n");
+
+        fs::write(path, output)
+    }
+
+    /// Saves the recording as `<name>.svg` in the `AOC_RECORD` directory, if set.
+    pub fn save(&self, name: &str) -> io::Result<()> {
+        match &self.output {
+            Some(dir) if !self.frames.is_empty() => {
+                fs::create_dir_all(dir)?;
+                self.save_animation(
+                    &dir.join(format!("{name}.svg")),
+                    16,
+                    Duration::from_millis(100),
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Color, Frame, Recorder, Style};
+    use crate::Matrix;
+    use std::{env, fs, process, time::Duration};
+
+    fn frame() -> Frame {
+        let matrix: Matrix<char> = "ab\n <".into();
+        Frame::from(&matrix)
+    }
+
+    #[test]
+    fn ansi() {
+        let mut frame = frame();
+        frame
+            .highlight(&[(1, 2).into()], Style::foreground(Color::RED))
+            .highlight(&[(1, 2).into()], Style::background(Color::BLUE));
+        assert_eq!(
+            frame.to_ansi(),
+            "a\x1b[38;2;220;50;47m\x1b[48;2;38;139;210mb\x1b[0m\n <\n"
+        );
+    }
+
+    #[test]
+    fn svg() {
+        let mut frame = frame();
+        frame
+            .highlight(
+                &[(2, 2).into(), (3, 1).into()],
+                Style::background(Color::GREEN),
+            )
+            .set_symbol((1, 1).into(), '&');
+        let svg = frame.to_svg(10);
+
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20""#)
+        );
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect ").count(), 4);
+        assert_eq!(svg.matches("<text ").count(), 3);
+        assert!(svg.contains(r##"<text x="5" y="5" fill="#000000">&amp;</text>"##));
+        assert!(svg.contains(r##"<rect x="10" y="10" width="10" height="10" fill="#859900"/>"##));
+        assert!(svg.contains(r##"<text x="15" y="15" fill="#000000">&lt;</text>"##));
+    }
+
+    #[test]
+    fn save_animation() {
+        let mut disabled = Recorder::disabled();
+        disabled.record(|| unreachable!("Disabled recorders never build frames."));
+        assert!(disabled.frames().is_empty());
+
+        let mut recorder = Recorder::new();
+        recorder.record(frame);
+        recorder.record(|| {
+            let mut frame = frame();
+            frame.set_symbol((1, 1).into(), 'z');
+            frame
+        });
+
+        let dir = env::temp_dir().join(format!("aoc-visualize-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("animation.svg");
+        recorder
+            .save_animation(&path, 10, Duration::from_millis(500))
+            .unwrap();
+        let svg = fs::read_to_string(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(svg.matches("<g ").count(), 2);
+        assert!(svg.contains(r#"begin="0s" dur="0.5s"/>"#));
+        assert!(svg.contains(r#"begin="0.5s"/>"#));
+        assert!(svg.contains(">z</text>"));
+    }
+}
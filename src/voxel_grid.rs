@@ -0,0 +1,156 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+};
+
+use num::{range_inclusive, PrimInt};
+
+use crate::{Cuboid, Position3};
+
+/// A sparse 3D grid stored as columns along the z axis, so the height of
+/// every xy column is available without scanning the whole grid.
+#[derive(Clone, Debug)]
+pub struct VoxelGrid<T, V> {
+    columns: HashMap<(T, T), BTreeMap<T, V>>,
+    len: usize,
+}
+
+impl<T, V> Default for VoxelGrid<T, V> {
+    fn default() -> Self {
+        Self {
+            columns: HashMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<T, V> VoxelGrid<T, V>
+where
+    T: Eq + Hash + Ord + Copy,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, position: Position3<T>, value: V) -> Option<V> {
+        let Position3 { x, y, z } = position;
+        let previous = self.columns.entry((x, y)).or_default().insert(z, value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    pub fn remove(&mut self, position: &Position3<T>) -> Option<V> {
+        let Position3 { x, y, z } = *position;
+        let column = self.columns.get_mut(&(x, y))?;
+        let removed = column.remove(&z)?;
+        if column.is_empty() {
+            self.columns.remove(&(x, y));
+        }
+        self.len -= 1;
+        Some(removed)
+    }
+
+    pub fn get(&self, position: &Position3<T>) -> Option<&V> {
+        let Position3 { x, y, z } = position;
+        self.columns.get(&(*x, *y))?.get(z)
+    }
+
+    pub fn contains(&self, position: &Position3<T>) -> bool {
+        self.get(position).is_some()
+    }
+
+    /// The highest occupied cell of the column at `(x, y)`.
+    pub fn top(&self, x: T, y: T) -> Option<(Position3<T>, &V)> {
+        let (&z, value) = self.columns.get(&(x, y))?.last_key_value()?;
+        Some((Position3::new(x, y, z), value))
+    }
+
+    pub fn height(&self, x: T, y: T) -> Option<T> {
+        self.top(x, y).map(|(position, _)| position.z)
+    }
+
+    /// The occupied cells of the column at `(x, y)` from bottom to top.
+    pub fn column(&self, x: T, y: T) -> impl Iterator<Item = (Position3<T>, &V)> {
+        self.columns
+            .get(&(x, y))
+            .into_iter()
+            .flat_map(move |column| {
+                column
+                    .iter()
+                    .map(move |(&z, value)| (Position3::new(x, y, z), value))
+            })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position3<T>, &V)> {
+        self.columns.iter().flat_map(|(&(x, y), column)| {
+            column
+                .iter()
+                .map(move |(&z, value)| (Position3::new(x, y, z), value))
+        })
+    }
+}
+
+impl<T, V> VoxelGrid<T, V>
+where
+    T: PrimInt + Hash,
+{
+    pub fn insert_cuboid(&mut self, cuboid: &Cuboid<T>, value: V)
+    where
+        V: Clone,
+    {
+        cuboid.positions().for_each(|position| {
+            self.insert(position, value.clone());
+        });
+    }
+
+    /// The highest occupied cell below the xy footprint of `cuboid`, i.e. the
+    /// height a falling `cuboid` would come to rest on.
+    pub fn height_under(&self, cuboid: &Cuboid<T>) -> Option<T> {
+        range_inclusive(cuboid.min.x, cuboid.max.x)
+            .flat_map(|x| range_inclusive(cuboid.min.y, cuboid.max.y).map(move |y| (x, y)))
+            .filter_map(|(x, y)| {
+                self.columns
+                    .get(&(x, y))?
+                    .range(..cuboid.min.z)
+                    .next_back()
+                    .map(|(&z, _)| z)
+            })
+            .max()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::VoxelGrid;
+    use crate::{Cuboid, Position3};
+
+    #[test]
+    fn column_heights() {
+        let mut grid = VoxelGrid::new();
+        grid.insert_cuboid(&Cuboid::new((0, 0, 1).into(), (2, 0, 1).into()), 'A');
+        grid.insert_cuboid(&Cuboid::new((1, 0, 2).into(), (1, 2, 2).into()), 'B');
+
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid.height(1, 0), Some(2));
+        assert_eq!(grid.height(0, 0), Some(1));
+        assert_eq!(grid.height(0, 1), None);
+        assert_eq!(grid.column(1, 0).map(|(_, &v)| v).collect::<String>(), "AB");
+
+        let falling = Cuboid::new((0, 0, 9).into(), (0, 2, 9).into());
+        assert_eq!(grid.height_under(&falling), Some(1));
+
+        assert_eq!(grid.remove(&Position3::new(1, 0, 2)), Some('B'));
+        assert_eq!(grid.height(1, 0), Some(1));
+        assert_eq!(grid.len(), 5);
+    }
+}
@@ -0,0 +1,54 @@
+// {{TITLE}}
+// https://adventofcode.com/{{YEAR}}/day/{{DAY}}
+use advent_of_code::Puzzle;
+use std::collections::HashMap;
+
+advent_of_code::solution!({{DAY}});
+
+/// The neighbors of every node, from lines like `a: b c`.
+type Graph<'a> = HashMap<&'a str, Vec<&'a str>>;
+
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Graph<'a>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Graph<'_> {
+        let mut graph = Graph::new();
+        input.lines().for_each(|line| {
+            let (node, neighbors) = line.split_once(':').unwrap();
+            neighbors.split_ascii_whitespace().for_each(|neighbor| {
+                graph.entry(node).or_default().push(neighbor);
+                graph.entry(neighbor).or_default().push(node);
+            });
+        });
+        graph
+    }
+
+    fn part_one(graph: &Graph<'_>) -> Option<u32> {
+        None
+    }
+
+    fn part_two(graph: &Graph<'_>) -> Option<u32> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, {{PART_ONE_ANSWER}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, {{PART_TWO_ANSWER}});
+    }
+}
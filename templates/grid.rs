@@ -0,0 +1,42 @@
+// {{TITLE}}
+// https://adventofcode.com/{{YEAR}}/day/{{DAY}}
+use advent_of_code::{Matrix, Puzzle};
+
+advent_of_code::solution!({{DAY}});
+
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Matrix<char>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Matrix<char> {
+        Matrix::from(input)
+    }
+
+    fn part_one(grid: &Matrix<char>) -> Option<u32> {
+        None
+    }
+
+    fn part_two(grid: &Matrix<char>) -> Option<u32> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, {{PART_ONE_ANSWER}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, {{PART_TWO_ANSWER}});
+    }
+}
@@ -0,0 +1,42 @@
+// {{TITLE}}
+// https://adventofcode.com/{{YEAR}}/day/{{DAY}}
+use advent_of_code::Puzzle;
+
+advent_of_code::solution!({{DAY}});
+
+struct Solution;
+
+impl Puzzle for Solution {
+    type Parsed<'a> = Vec<&'a str>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        input.lines().collect()
+    }
+
+    fn part_one(lines: &Self::Parsed<'_>) -> Option<u32> {
+        None
+    }
+
+    fn part_two(lines: &Self::Parsed<'_>) -> Option<u32> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, {{PART_ONE_ANSWER}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, {{PART_TWO_ANSWER}});
+    }
+}